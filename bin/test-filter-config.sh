@@ -0,0 +1,11 @@
+#!/usr/bin/env bash
+
+set -euxo pipefail
+
+export RUST_BACKTRACE=1
+
+# The crate's `.cargo/config.toml` points CHRONO_TZ_FILTER_CONFIG to its
+# `chrono-tz.toml`.
+cd chrono-tz/tests/check-filter-config
+
+cargo test --color=always -- --color=always
@@ -0,0 +1,13 @@
+#!/usr/bin/env bash
+
+set -euxo pipefail
+
+export RUST_BACKTRACE=1
+export CHRONO_TZ_YEAR_RANGE='1970..2060'
+
+cd chrono-tz/tests/check-year-range
+
+for edges in clamp -00
+do
+  CHRONO_TZ_YEAR_RANGE_EDGES="$edges" cargo test --color=always -- --color=always
+done
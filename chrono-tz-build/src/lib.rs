@@ -1,24 +1,53 @@
 extern crate parse_zoneinfo;
 #[cfg(feature = "filter-by-regex")]
 extern crate regex;
+#[cfg(feature = "filter-by-config")]
+extern crate toml;
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::env;
+use std::fmt::Display;
 use std::fs::File;
 use std::io::{self, BufRead, BufReader, Write};
+use std::iter;
 use std::path::{Path, PathBuf};
 
-use parse_zoneinfo::line::Line;
+use parse_zoneinfo::form::{Converter, DataForm};
+use parse_zoneinfo::line::{ChangeTime, Line, Year};
+use parse_zoneinfo::posix::{PosixDay, PosixRule, PosixTz};
 use parse_zoneinfo::structure::{Child, Structure};
-use parse_zoneinfo::table::{Table, TableBuilder};
-use parse_zoneinfo::transitions::FixedTimespan;
-use parse_zoneinfo::transitions::TableTransitions;
+use parse_zoneinfo::tab::{Coordinates, CountryLine, ZoneTabLine};
+use parse_zoneinfo::table::{LeapSecond, Table, TableBuilder};
+use parse_zoneinfo::transitions::{FixedTimespan, FixedTimespanSet, TableTransitions, YEARS};
 use parse_zoneinfo::FILES;
 
 /// The name of the environment variable which possibly holds the filter regex.
 #[cfg(feature = "filter-by-regex")]
 pub const FILTER_ENV_VAR_NAME: &str = "CHRONO_TZ_TIMEZONE_FILTER";
 
+/// The name of the environment variable which possibly holds the path of a
+/// filter config file, such as `chrono-tz.toml`.
+#[cfg(feature = "filter-by-config")]
+pub const FILTER_CONFIG_ENV_VAR_NAME: &str = "CHRONO_TZ_FILTER_CONFIG";
+
+/// The name of the environment variable which possibly holds the form of the
+/// tz data to build from: `main`, `vanguard` or `rearguard`.
+pub const DATA_FORM_ENV_VAR_NAME: &str = "CHRONO_TZ_DATA_FORM";
+
+/// The name of the environment variable which possibly holds the path of a
+/// tzdata directory to build from instead of the bundled one.
+pub const TZDATA_DIR_ENV_VAR_NAME: &str = "CHRONO_TZ_TZDATA_DIR";
+
+/// The name of the environment variable which possibly holds the range of
+/// years to keep the transitions of, such as `1970..2060`.
+pub const YEAR_RANGE_ENV_VAR_NAME: &str = "CHRONO_TZ_YEAR_RANGE";
+
+/// The name of the environment variable which possibly holds what the
+/// offsets are outside of the year range: `clamp` to keep the ones at its
+/// edges in effect, which is the default, or `-00` to leave them unspecified
+/// like zic does.
+pub const YEAR_RANGE_EDGES_ENV_VAR_NAME: &str = "CHRONO_TZ_YEAR_RANGE_EDGES";
+
 // This function is needed until zoneinfo_parse handles comments correctly.
 // Technically a '#' symbol could occur between double quotes and should be
 // ignored in this case, however this never happens in the tz database as it
@@ -30,28 +59,145 @@ fn strip_comments(mut line: String) -> String {
     line
 }
 
-// Generate a list of the time zone periods beyond the first that apply
-// to this zone, as a string representation of a static slice.
-fn format_rest(rest: Vec<(i64, FixedTimespan)>) -> String {
-    let mut ret = "&[\n".to_string();
-    for (
-        start,
-        FixedTimespan {
-            utc_offset,
-            dst_offset,
-            name,
-        },
-    ) in rest
-    {
-        ret.push_str(&format!(
-            "                ({start}, FixedTimespan {{ \
-             offset: {offset}, name: {name:?} \
-             }}),\n",
-            offset = utc_offset + dst_offset,
-        ));
+// A timespan as the key it's deduplicated by in the `TIMESPANS` table.
+type TimespanKey = (i64, i64, String);
+
+fn timespan_key(timespan: &FixedTimespan) -> TimespanKey {
+    (
+        timespan.utc_offset,
+        timespan.dst_offset,
+        timespan.name.clone(),
+    )
+}
+
+// The number of transitions in each block of the packed form of a zone,
+// which has to match `BLOCK_LEN` in chrono-tz.
+const BLOCK_LEN: usize = 8;
+
+// The packed form of the time zone periods of a zone, as the string
+// representations of the fields of its `FixedTimespanSet`.
+struct Packed {
+    // The indices in `TIMESPANS` of the timespans the zone uses, in order
+    // of appearance.
+    types: String,
+    // The transitions, as varint-encoded deltas between their times, each
+    // followed by the position in `types` of the timespan that takes
+    // effect.
+    rest: String,
+    // The time of the first transition of each block of `BLOCK_LEN`, which
+    // is left out of `rest` for lookups to start decoding there.
+    blocks: String,
+    // Where each block starts in `rest`.
+    offsets: String,
+}
+
+fn pack(timespans: &FixedTimespanSet, indices: &BTreeMap<TimespanKey, usize>) -> Packed {
+    let mut types = Vec::new();
+    let mut type_of = |timespan: &FixedTimespan| {
+        let index = indices[&timespan_key(timespan)];
+        let position = match types.iter().position(|&i| i == index) {
+            Some(position) => position,
+            None => {
+                types.push(index);
+                types.len() - 1
+            }
+        };
+        u8::try_from(position).expect("too many timespans in a zone")
+    };
+
+    type_of(&timespans.first);
+    let mut rest = Vec::new();
+    let mut blocks = Vec::new();
+    let mut offsets = Vec::new();
+    let mut previous = 0;
+    for (i, (start, timespan)) in timespans.rest.iter().enumerate() {
+        if i % BLOCK_LEN == 0 {
+            blocks.push(*start);
+            offsets.push(u16::try_from(rest.len()).expect("too many transitions in a zone"));
+        } else {
+            write_varint(&mut rest, start - previous);
+        }
+        rest.push(type_of(timespan));
+        previous = *start;
     }
-    ret.push_str("            ]");
-    ret
+
+    Packed {
+        types: format_list(&types),
+        rest: format!(
+            "b\"{}\"",
+            rest.iter()
+                .map(|byte| format!("\\x{byte:02x}"))
+                .collect::<String>()
+        ),
+        blocks: format_list(&blocks),
+        offsets: format_list(&offsets),
+    }
+}
+
+fn format_list<T: Display>(values: &[T]) -> String {
+    let values = values
+        .iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>();
+    format!("&[{}]", values.join(", "))
+}
+
+// Append a number as a zigzag-encoded LEB128 varint, which is what the
+// `Rest` iterator of chrono-tz decodes.
+fn write_varint(bytes: &mut Vec<u8>, value: i64) {
+    let mut value = ((value << 1) ^ (value >> 63)) as u64;
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+// Generate the recurring rules that apply after the last time zone period,
+// as a string representation of an `Option<PosixTz>`. Zones that stay on
+// standard time don't need any.
+fn format_footer(footer: Option<PosixTz>) -> String {
+    let Some(PosixTz {
+        std,
+        dst: Some(dst),
+    }) = footer
+    else {
+        return "None".to_string();
+    };
+
+    format!(
+        "Some(PosixTz {{
+                std: {std},
+                dst: Some(PosixDst {{
+                    timespan: {dst},
+                    start: {start},
+                    end: {end},
+                }}),
+            }})",
+        std = format_timespan(&std),
+        dst = format_timespan(&dst.timespan),
+        start = format_posix_rule(&dst.start),
+        end = format_posix_rule(&dst.end),
+    )
+}
+
+fn format_timespan(timespan: &FixedTimespan) -> String {
+    format!(
+        "FixedTimespan {{ utc_offset: {}, dst_offset: {}, name: {:?} }}",
+        timespan.utc_offset, timespan.dst_offset, timespan.name,
+    )
+}
+
+fn format_posix_rule(rule: &PosixRule) -> String {
+    let day = match rule.day {
+        PosixDay::Julian(day) => format!("PosixDay::Julian({day})"),
+        PosixDay::Zero(day) => format!("PosixDay::Zero({day})"),
+        PosixDay::MonthWeekday(month, week, weekday) => format!(
+            "PosixDay::MonthWeekday({}, {week}, {})",
+            month as u8, weekday as u8
+        ),
+    };
+    format!("PosixRule {{ day: {day}, time: {} }}", rule.time)
 }
 
 // Convert all '/' to '__', all '+' to 'Plus' and '-' to 'Minus', unless
@@ -75,15 +221,40 @@ fn convert_bad_chars(name: &str) -> String {
     }
 }
 
+// The name of the zone a link points to, or the name itself for a zone.
+fn canonical<'a>(table: &'a Table, zone: &'a str) -> &'a str {
+    table.links.get(zone).map_or(zone, |target| target.as_str())
+}
+
 // The timezone file contains impls of `Timespans` for all timezones in the
 // database. The `Wrap` wrapper in the `timezone_impl` module then implements
 // TimeZone for any contained struct that implements `Timespans`.
-fn write_timezone_file(timezone_file: &mut File, table: &Table, uncased: bool) -> io::Result<()> {
+fn write_timezone_file(
+    timezone_file: &mut File,
+    table: &Table,
+    windows_zones: &[WindowsZone],
+    zone_tables: &ZoneTables,
+    backward: &BackwardLinks,
+    uncased: bool,
+    year_range: Option<&YearRange>,
+) -> io::Result<()> {
     let zones = table
         .zonesets
         .keys()
         .chain(table.links.keys())
         .collect::<BTreeSet<_>>();
+    let mut timespans = BTreeMap::new();
+    for zone in &zones {
+        if table.links.contains_key(zone.as_str()) {
+            continue;
+        }
+        let mut set = table.timespans(zone).unwrap();
+        let mut footer = table.posix_tz(zone);
+        if let Some(year_range) = year_range {
+            year_range.trim(&mut set, &mut footer);
+        }
+        timespans.insert(zone.as_str(), (set, footer));
+    }
     writeln!(
         timezone_file,
         "use core::fmt::{{self, Debug, Display, Formatter}};",
@@ -91,8 +262,20 @@ fn write_timezone_file(timezone_file: &mut File, table: &Table, uncased: bool) -
     writeln!(timezone_file, "use core::str::FromStr;\n",)?;
     writeln!(
         timezone_file,
-        "use crate::timezone_impl::{{TimeSpans, FixedTimespanSet, FixedTimespan}};\n",
+        "use crate::timezone_impl::{{TimeSpans, FixedTimespanSet, FixedTimespan}};",
     )?;
+    writeln!(timezone_file, "use crate::leap::LeapSecond;")?;
+    let footers = timespans
+        .iter()
+        .map(|(&zone, (_, footer))| (zone, format_footer(footer.clone())))
+        .collect::<BTreeMap<_, _>>();
+    if footers.values().any(|footer| footer != "None") {
+        writeln!(
+            timezone_file,
+            "use crate::posix::{{PosixDay, PosixDst, PosixRule, PosixTz}};",
+        )?;
+    }
+    writeln!(timezone_file)?;
     writeln!(
         timezone_file,
         "/// TimeZones built at compile time from the tz database
@@ -115,7 +298,10 @@ fn write_timezone_file(timezone_file: &mut File, table: &Table, uncased: bool) -
 
     let mut map = phf_codegen::Map::new();
     for zone in &zones {
-        map.entry(zone, format!("Tz::{}", convert_bad_chars(zone)));
+        map.entry(zone.as_str(), format!("Tz::{}", convert_bad_chars(zone)));
+    }
+    for (alias, target) in backward.excluded() {
+        map.entry(alias.as_str(), format!("Tz::{}", convert_bad_chars(target)));
     }
     writeln!(
         timezone_file,
@@ -133,6 +319,12 @@ fn write_timezone_file(timezone_file: &mut File, table: &Table, uncased: bool) -
                 format!("Tz::{}", convert_bad_chars(zone)),
             );
         }
+        for (alias, target) in backward.excluded() {
+            map.entry(
+                uncased::UncasedStr::new(alias),
+                format!("Tz::{}", convert_bad_chars(target)),
+            );
+        }
         writeln!(
             timezone_file,
             "static TIMEZONES_UNCASED: ::phf::Map<&'static uncased::UncasedStr, Tz> = \n{};",
@@ -179,6 +371,8 @@ impl FromStr for Tz {{
     }}"
     )?;
 
+    write_links(timezone_file, table, &zones, backward)?;
+
     if uncased {
         writeln!(
             timezone_file,
@@ -209,27 +403,53 @@ impl FromStr for Tz {{
     }}
 }}\n"
     )?;
+    let indices = timespans
+        .values()
+        .map(|(set, _)| set)
+        .flat_map(|set| iter::once(&set.first).chain(set.rest.iter().map(|(_, span)| span)))
+        .map(timespan_key)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .enumerate()
+        .map(|(index, key)| (key, index))
+        .collect::<BTreeMap<_, _>>();
+    writeln!(
+        timezone_file,
+        "/// The distinct timespans of all the zones, that `FixedTimespanSet`s refer to by index
+pub(crate) static TIMESPANS: [FixedTimespan; {}] = [",
+        indices.len(),
+    )?;
+    for (utc_offset, dst_offset, name) in indices.keys() {
+        writeln!(
+            timezone_file,
+            "    FixedTimespan {{ utc_offset: {utc_offset}, dst_offset: {dst_offset}, name: {name:?} }},",
+        )?;
+    }
+    writeln!(timezone_file, "];\n")?;
+
     writeln!(
         timezone_file,
         "impl TimeSpans for Tz {{
     fn timespans(&self) -> FixedTimespanSet {{"
     )?;
-    for zone in &zones {
-        if table.links.get(zone.as_str()).is_some() {
-            continue;
-        }
-        let zone_name = convert_bad_chars(zone);
-        let timespans = table.timespans(zone).unwrap();
+    for (zone, (set, _)) in &timespans {
+        let Packed {
+            types,
+            rest,
+            blocks,
+            offsets,
+        } = pack(set, &indices);
         writeln!(
             timezone_file,
             "        const {zone}: FixedTimespanSet = FixedTimespanSet {{
-            first: FixedTimespan {{ offset: {offset}, name: {name:?} }},
+            types: {types},
             rest: {rest},
+            blocks: {blocks},
+            offsets: {offsets},
+            footer: {footer},
         }};\n",
-            zone = zone_name.to_uppercase(),
-            rest = format_rest(timespans.rest),
-            offset = timespans.first.utc_offset + timespans.first.dst_offset,
-            name = timespans.first.name,
+            zone = convert_bad_chars(zone).to_uppercase(),
+            footer = footers[zone],
         )?;
     }
 
@@ -280,18 +500,241 @@ pub static TZ_VARIANTS: [Tz; {num}] = [
             zone = convert_bad_chars(zone)
         )?;
     }
-    write!(timezone_file, "];")?;
+    writeln!(timezone_file, "];\n")?;
+
+    let canonical_zones = zones
+        .iter()
+        .filter(|zone| !table.links.contains_key(zone.as_str()))
+        .collect::<Vec<_>>();
+    write!(
+        timezone_file,
+        "/// An array of every variant that isn't a link
+///
+/// Useful for listing each timezone under a single name:
+///
+/// ```
+/// use chrono_tz::{{CANONICAL_VARIANTS, Tz}};
+/// assert!(CANONICAL_VARIANTS.contains(&Tz::Etc__UTC));
+/// assert!(!CANONICAL_VARIANTS.contains(&Tz::UTC));
+/// ```
+pub static CANONICAL_VARIANTS: [Tz; {num}] = [
+",
+        num = canonical_zones.len()
+    )?;
+    for zone in canonical_zones {
+        writeln!(timezone_file, "    Tz::{},", convert_bad_chars(zone))?;
+    }
+    writeln!(timezone_file, "];\n")?;
+
+    write_windows_zones(timezone_file, table, &zones, windows_zones, backward)?;
+    write_countries(timezone_file, table, &zones, zone_tables)?;
+    write_coordinates(timezone_file, table, &zones, zone_tables)?;
+    write_leap_seconds(timezone_file, table)
+}
+
+// A link is merely another name for the zone it points to: `canonical()` maps
+// each link to that zone, and `aliases()` lists all the other names of a zone.
+fn write_links(
+    timezone_file: &mut File,
+    table: &Table,
+    zones: &BTreeSet<&String>,
+    backward: &BackwardLinks,
+) -> io::Result<()> {
+    writeln!(
+        timezone_file,
+        "
+    /// Returns the zone this timezone is a link to, or the timezone itself if it isn't a link
+    ///
+    /// Two timezones with the same canonical zone always have the same offsets, so this can be
+    /// used to tell whether they are the same.
+    ///
+    /// ```
+    /// use chrono_tz::Tz;
+    ///
+    /// assert_eq!(Tz::UTC.canonical(), Tz::Etc__UTC);
+    /// assert_eq!(Tz::Etc__UTC.canonical(), Tz::Etc__UTC);
+    /// ```
+    pub fn canonical(self) -> Tz {{
+        match self {{"
+    )?;
+    let mut links = BTreeMap::<&str, Vec<&str>>::new();
+    for zone in zones {
+        if let Some(target) = table.links.get(zone.as_str()) {
+            writeln!(
+                timezone_file,
+                "            Tz::{} => Tz::{},",
+                convert_bad_chars(zone),
+                convert_bad_chars(target)
+            )?;
+            links.entry(target).or_default().push(zone);
+        }
+    }
+    writeln!(
+        timezone_file,
+        "            _ => self,
+        }}
+    }}
+
+    /// Returns whether this timezone is a link, that is another name for a zone
+    ///
+    /// ```
+    /// use chrono_tz::Tz;
+    ///
+    /// assert!(Tz::UTC.is_link());
+    /// assert!(!Tz::Etc__UTC.is_link());
+    /// ```
+    pub fn is_link(self) -> bool {{
+        self.canonical() != self
+    }}
+
+    /// Returns the other names of this timezone: the zone it links to and every other link to
+    /// that zone
+    ///
+    /// ```
+    /// use chrono_tz::Tz;
+    ///
+    /// assert!(Tz::Etc__UTC.aliases().contains(&Tz::UTC));
+    /// assert!(Tz::UTC.aliases().contains(&Tz::Etc__UTC));
+    /// ```
+    pub fn aliases(self) -> &'static [Tz] {{
+        match self {{"
+    )?;
+    for (target, links) in &links {
+        for zone in std::iter::once(target).chain(links) {
+            let aliases = std::iter::once(target)
+                .chain(links)
+                .filter(|alias| alias != &zone)
+                .map(|alias| format!("Tz::{}", convert_bad_chars(alias)))
+                .collect::<Vec<_>>();
+            writeln!(
+                timezone_file,
+                "            Tz::{} => &[{}],",
+                convert_bad_chars(zone),
+                aliases.join(", ")
+            )?;
+        }
+    }
+    // A filtered table can consist of nothing but zones with aliases.
+    let aliased = links.values().map(|links| links.len() + 1).sum::<usize>();
+    if aliased < zones.len() {
+        writeln!(timezone_file, "            _ => &[],")?;
+    }
+    writeln!(
+        timezone_file,
+        "        }}
+    }}
+
+    /// Returns whether this timezone is one of the links the tz database only keeps for
+    /// backward compatibility
+    ///
+    /// These come from its `backward` file, and are names that have fallen out of use, such as
+    /// `US/Eastern` or `Asia/Calcutta`. New code should use the zone they link to instead, as
+    /// returned by [`Tz::canonical`].
+    pub fn is_deprecated_alias(self) -> bool {{"
+    )?;
+    let deprecated = zones
+        .iter()
+        .filter(|zone| backward.links.contains_key(zone.as_str()))
+        .map(|zone| format!("Tz::{}", convert_bad_chars(zone)))
+        .collect::<Vec<_>>();
+    match deprecated.is_empty() {
+        true => writeln!(timezone_file, "        false")?,
+        false => writeln!(
+            timezone_file,
+            "        matches!(\n            self,\n            {}\n        )",
+            deprecated.join("\n                | ")
+        )?,
+    }
+    writeln!(timezone_file, "    }}")
+}
+
+// The Windows mappings are looked up in both directions: from a Windows ID
+// to the zones of each territory, and from a zone name to its Windows ID.
+fn write_windows_zones(
+    timezone_file: &mut File,
+    table: &Table,
+    zones: &BTreeSet<&String>,
+    windows_zones: &[WindowsZone],
+    backward: &BackwardLinks,
+) -> io::Result<()> {
+    let mut territories = BTreeMap::<&str, Vec<String>>::new();
+    for windows_zone in windows_zones {
+        // The first zone listed for a territory is its preferred one.
+        let zone = backward.resolve(&windows_zone.zones[0]);
+        if !zones.iter().any(|name| *name == zone) {
+            continue;
+        }
+        territories
+            .entry(&windows_zone.windows)
+            .or_default()
+            .push(format!(
+                "({:?}, Tz::{})",
+                windows_zone.territory,
+                convert_bad_chars(zone)
+            ));
+    }
+
+    let mut map = phf_codegen::Map::new();
+    for (windows, entries) in &territories {
+        map.entry(*windows, format!("&[{}]", entries.join(", ")));
+    }
+    writeln!(
+        timezone_file,
+        "pub(crate) static WINDOWS_ZONES: ::phf::Map<&'static str, &'static [(&'static str, Tz)]> = \n{};\n",
+        map.build()
+    )?;
+
+    // CLDR names some zones by what is now a link in the tz database, so
+    // every zone linked to the same target shares its Windows ID, unless
+    // CLDR names it directly. Excluded links only count towards their target.
+    let mut ids = BTreeMap::new();
+    let mut canonical_ids = BTreeMap::new();
+    for windows_zone in windows_zones {
+        for zone in &windows_zone.zones {
+            let resolved = backward.resolve(zone);
+            if resolved == zone {
+                ids.insert(zone.as_str(), windows_zone.windows.as_str());
+            }
+            canonical_ids
+                .entry(canonical(table, resolved))
+                .or_insert(windows_zone.windows.as_str());
+        }
+    }
+
+    let mut map = phf_codegen::Map::new();
+    for zone in zones {
+        let windows = ids
+            .get(zone.as_str())
+            .or_else(|| canonical_ids.get(canonical(table, zone)));
+        if let Some(windows) = windows {
+            map.entry(zone.as_str(), format!("{windows:?}"));
+        }
+    }
+    writeln!(
+        timezone_file,
+        "pub(crate) static WINDOWS_IDS: ::phf::Map<&'static str, &'static str> = \n{};",
+        map.build()
+    )?;
     Ok(())
 }
 
 // Create a file containing nice-looking re-exports such as Europe::London
 // instead of having to use chrono_tz::timezones::Europe__London
-fn write_directory_file(directory_file: &mut File, table: &Table, version: &str) -> io::Result<()> {
-    // expose the underlying IANA TZDB version
+fn write_directory_file(
+    directory_file: &mut File,
+    table: &Table,
+    version: &str,
+    form: DataForm,
+) -> io::Result<()> {
+    // expose the underlying IANA TZDB version, and the form of its data
     writeln!(
         directory_file,
         "pub const IANA_TZDB_VERSION: &str = \"{version}\";\n"
     )?;
+    writeln!(
+        directory_file,
+        "pub const IANA_TZDB_DATA_FORM: &str = \"{form}\";\n"
+    )?;
     // add the `loose' zone definitions first
     writeln!(directory_file, "use crate::timezones::Tz;\n")?;
     let zones = table
@@ -315,7 +758,7 @@ fn write_directory_file(directory_file: &mut File, table: &Table, version: &str)
 
         match first {
             true => first = false,
-            false => writeln!(directory_file, "")?,
+            false => writeln!(directory_file)?,
         }
 
         let module_name = convert_bad_chars(entry.name);
@@ -365,20 +808,382 @@ fn write_directory_file(directory_file: &mut File, table: &Table, version: &str)
     Ok(())
 }
 
-/// Module containing code supporting filter-by-regex feature
+/// A `mapZone` element of CLDR's `windowsZones.xml`, which maps a Windows
+/// time zone ID to the zones it stands for in one territory.
+struct WindowsZone {
+    windows: String,
+    territory: String,
+    zones: Vec<String>,
+}
+
+fn read_windows_zones(path: &Path) -> Vec<WindowsZone> {
+    let file = File::open(path).unwrap_or_else(|e| panic!("cannot open {}: {e}", path.display()));
+
+    // Every element sits on a line of its own, so there is no need for a
+    // full XML parser.
+    let mut windows_zones = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line.unwrap();
+        if !line.trim_start().starts_with("<mapZone ") {
+            continue;
+        }
+
+        let attribute = |name: &str| {
+            let start = line
+                .find(&format!(" {name}=\""))
+                .unwrap_or_else(|| panic!("no {name} attribute in {line:?}"))
+                + name.len()
+                + 3;
+            let len = line[start..].find('"').unwrap();
+            line[start..start + len].to_owned()
+        };
+        windows_zones.push(WindowsZone {
+            windows: attribute("other"),
+            territory: attribute("territory"),
+            zones: attribute("type")
+                .split_whitespace()
+                .map(str::to_owned)
+                .collect(),
+        });
+    }
+
+    windows_zones
+}
+
+/// The countries of `iso3166.tab`, and the zones `zone.tab` and
+/// `zone1970.tab` list for them along with their coordinates.
+struct ZoneTables {
+    names: Vec<(String, String)>,
+    zone_tab: Vec<(String, String)>,
+    zone1970_tab: Vec<(Vec<String>, String)>,
+    coordinates: BTreeMap<String, Coordinates>,
+}
+
+fn read_zone_tables(tz_dir: &Path) -> ZoneTables {
+    let lines = |name: &str| {
+        let path = tz_dir.join(name);
+        let file =
+            File::open(&path).unwrap_or_else(|e| panic!("cannot open {}: {e}", path.display()));
+        BufReader::new(file).lines().map(Result::unwrap)
+    };
+    let zone_tab = |name: &str| {
+        lines(name).filter_map(|line| {
+            let line = ZoneTabLine::new(&line).unwrap()?;
+            let countries = line.countries.iter().map(|c| c.to_string()).collect();
+            Some((countries, line.coordinates, line.zone.to_owned()))
+        })
+    };
+
+    let mut coordinates = BTreeMap::new();
+    let zone1970_tab = zone_tab("zone1970.tab")
+        .map(|(countries, point, zone)| {
+            coordinates.insert(zone.clone(), point);
+            (countries, zone)
+        })
+        .collect();
+    let zone_tab = zone_tab("zone.tab")
+        .map(|(mut countries, point, zone): (Vec<_>, _, _)| {
+            coordinates.entry(zone.clone()).or_insert(point);
+            (countries.remove(0), zone)
+        })
+        .collect();
+
+    ZoneTables {
+        names: lines("iso3166.tab")
+            .filter_map(|line| {
+                let line = CountryLine::new(&line).unwrap()?;
+                Some((line.code.to_owned(), line.name.to_owned()))
+            })
+            .collect(),
+        zone_tab,
+        zone1970_tab,
+        coordinates,
+    }
+}
+
+// Each country gets the zones `zone.tab` lists for it, in order, followed by
+// the zones `zone1970.tab` adds that aren't the target of one of those.
+fn write_countries(
+    timezone_file: &mut File,
+    table: &Table,
+    zones: &BTreeSet<&String>,
+    zone_tables: &ZoneTables,
+) -> io::Result<()> {
+    let mut country_zones = BTreeMap::<&str, Vec<&str>>::new();
+    for (country, zone) in &zone_tables.zone_tab {
+        let list = country_zones.entry(country).or_default();
+        if !list.contains(&zone.as_str()) {
+            list.push(zone);
+        }
+    }
+    for (codes, zone) in &zone_tables.zone1970_tab {
+        for country in codes {
+            let list = country_zones.entry(country).or_default();
+            if !list.iter().any(|z| canonical(table, z) == zone) {
+                list.push(zone);
+            }
+        }
+    }
+    for list in country_zones.values_mut() {
+        list.retain(|zone| zones.contains(&zone.to_string()));
+    }
+
+    let mut zone_countries = BTreeMap::<&str, Vec<&str>>::new();
+    for (country, list) in &country_zones {
+        for zone in list {
+            zone_countries.entry(zone).or_default().push(country);
+        }
+    }
+
+    write!(
+        timezone_file,
+        "
+/// Every country in the tz database, as pairs of ISO 3166 code and name
+///
+/// ```
+/// use chrono_tz::COUNTRIES;
+/// assert!(COUNTRIES.contains(&(\"BR\", \"Brazil\")));
+/// ```
+pub static COUNTRIES: [(&str, &str); {num}] = [
+",
+        num = zone_tables.names.len()
+    )?;
+    for (code, name) in &zone_tables.names {
+        writeln!(timezone_file, "    ({code:?}, {name:?}),")?;
+    }
+    writeln!(timezone_file, "];\n")?;
+
+    let mut map = phf_codegen::Map::new();
+    for (code, name) in &zone_tables.names {
+        map.entry(code.as_str(), format!("{name:?}"));
+    }
+    writeln!(
+        timezone_file,
+        "pub(crate) static COUNTRY_NAMES: ::phf::Map<&'static str, &'static str> = \n{};\n",
+        map.build()
+    )?;
+
+    let mut map = phf_codegen::Map::new();
+    for (country, list) in &country_zones {
+        let list = list
+            .iter()
+            .map(|zone| format!("Tz::{}", convert_bad_chars(zone)))
+            .collect::<Vec<_>>();
+        map.entry(*country, format!("&[{}]", list.join(", ")));
+    }
+    writeln!(
+        timezone_file,
+        "pub(crate) static COUNTRY_ZONES: ::phf::Map<&'static str, &'static [Tz]> = \n{};\n",
+        map.build()
+    )?;
+
+    let mut map = phf_codegen::Map::new();
+    for (zone, list) in &zone_countries {
+        map.entry(*zone, format!("&{list:?}"));
+    }
+    writeln!(
+        timezone_file,
+        "pub(crate) static ZONE_COUNTRIES: ::phf::Map<&'static str, &'static [&'static str]> = \n{};",
+        map.build()
+    )?;
+    Ok(())
+}
+
+// Links without coordinates of their own share those of their target. The
+// points are also listed by latitude, for the nearest zone lookup to search.
+fn write_coordinates(
+    timezone_file: &mut File,
+    table: &Table,
+    zones: &BTreeSet<&String>,
+    zone_tables: &ZoneTables,
+) -> io::Result<()> {
+    let coordinates = &zone_tables.coordinates;
+    let mut map = phf_codegen::Map::new();
+    for zone in zones {
+        let point = coordinates
+            .get(zone.as_str())
+            .or_else(|| coordinates.get(canonical(table, zone)));
+        if let Some(point) = point {
+            map.entry(
+                zone.as_str(),
+                format!("({}, {})", point.latitude, point.longitude),
+            );
+        }
+    }
+    writeln!(
+        timezone_file,
+        "\npub(crate) static ZONE_COORDINATES: ::phf::Map<&'static str, (i32, i32)> = \n{};\n",
+        map.build()
+    )?;
+
+    let mut points = coordinates
+        .iter()
+        .filter(|(zone, _)| zones.contains(zone))
+        .collect::<Vec<_>>();
+    points.sort_by_key(|(zone, point)| (point.latitude, point.longitude, *zone));
+    writeln!(
+        timezone_file,
+        "pub(crate) static ZONES_BY_LATITUDE: [(i32, i32, Tz); {}] = [",
+        points.len()
+    )?;
+    for (zone, point) in points {
+        writeln!(
+            timezone_file,
+            "    ({}, {}, Tz::{}),",
+            point.latitude,
+            point.longitude,
+            convert_bad_chars(zone)
+        )?;
+    }
+    writeln!(timezone_file, "];")
+}
+
+// Each leap second comes with the difference between TAI and UTC from then
+// on, rather than the single second it adds or removes.
+fn write_leap_seconds(timezone_file: &mut File, table: &Table) -> io::Result<()> {
+    // TAI was 10 seconds ahead of UTC when leap seconds began, in 1972.
+    let mut tai_offset = 10;
+    writeln!(
+        timezone_file,
+        "\npub(crate) static LEAP_SECONDS: [LeapSecond; {}] = [",
+        table.leap_seconds.len()
+    )?;
+    for leap_second in &table.leap_seconds {
+        tai_offset += leap_second.correction;
+        writeln!(
+            timezone_file,
+            "    LeapSecond {{ timestamp: {}, tai_offset: {tai_offset} }},",
+            leap_second.timestamp
+        )?;
+    }
+    writeln!(timezone_file, "];")?;
+    writeln!(
+        timezone_file,
+        "pub(crate) static LEAP_SECONDS_EXPIRY: Option<i64> = {:?};",
+        table.leap_seconds_expiry
+    )
+}
+
+/// The links of the `backward` file, which only exist so that names that
+/// have fallen out of use go on working.
+struct BackwardLinks {
+    /// The name of each link, along with that of the zone it links to.
+    links: BTreeMap<String, String>,
+    /// Whether the links are left out of `Tz`, and only accepted as names of
+    /// the zone they link to when parsing.
+    excluded: bool,
+}
+
+impl BackwardLinks {
+    /// Finds the links with the given names in `table`, and takes them out of
+    /// it if they are to be excluded.
+    ///
+    /// The `UTC` link always stays, as `chrono_tz::UTC` is so widely used.
+    fn new(table: &mut Table, names: BTreeSet<String>, excluded: bool) -> Self {
+        let mut links = BTreeMap::new();
+        for name in names {
+            let target = match table.links.get(&name) {
+                Some(target) => target.clone(),
+                None => continue,
+            };
+            if excluded && name != "UTC" {
+                table.links.remove(&name);
+            }
+            links.insert(name, target);
+        }
+        BackwardLinks { links, excluded }
+    }
+
+    /// The links that were taken out of the table, with their targets.
+    fn excluded(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.links
+            .iter()
+            .filter(move |(name, _)| self.excluded && name.as_str() != "UTC")
+    }
+
+    /// Returns the zone an excluded link links to, or the name itself for
+    /// anything else.
+    fn resolve<'a>(&'a self, zone: &'a str) -> &'a str {
+        match self.excluded().find(|(name, _)| *name == zone) {
+            Some((_, target)) => target,
+            None => zone,
+        }
+    }
+}
+
+/// Module containing code supporting the filter-by-regex and filter-by-config
+/// features
 ///
 /// The "GMT" and "UTC" time zones are always included.
 #[cfg(feature = "filter-by-regex")]
 mod filter {
+    #[cfg(feature = "filter-by-config")]
+    use std::collections::BTreeMap;
     use std::collections::HashSet;
     use std::env;
+    #[cfg(feature = "filter-by-config")]
+    use std::path::PathBuf;
 
     use regex::Regex;
 
+    #[cfg(feature = "filter-by-config")]
+    use crate::FILTER_CONFIG_ENV_VAR_NAME;
     use crate::{Table, FILTER_ENV_VAR_NAME};
 
-    /// Filter `table` by applying [`FILTER_ENV_VAR_NAME`].
-    pub(crate) fn maybe_filter_timezone_table(table: &mut Table) {
+    /// The names of the time zones and links to keep.
+    #[cfg(feature = "filter-by-config")]
+    #[derive(Default)]
+    struct Filter {
+        /// Patterns matching the names to keep.
+        include: Vec<Regex>,
+        /// Patterns matching the names to leave out, even if they would be
+        /// kept otherwise.
+        exclude: Vec<Regex>,
+        /// The zones of the countries to keep, from `zone.tab`.
+        zones: HashSet<String>,
+        /// The regions to keep, such as `Europe` or `America/Argentina`.
+        regions: Vec<String>,
+    }
+
+    #[cfg(feature = "filter-by-config")]
+    impl Filter {
+        /// Whether nothing in particular is asked for, so that everything is
+        /// kept but what is excluded.
+        fn keeps_all(&self) -> bool {
+            self.include.is_empty() && self.zones.is_empty() && self.regions.is_empty()
+        }
+
+        fn includes(&self, name: &str) -> bool {
+            self.include.iter().any(|regex| regex.is_match(name))
+                || self.zones.contains(name)
+                || self.regions.iter().any(|region| {
+                    name.strip_prefix(region.as_str())
+                        .map_or(false, |rest| rest.starts_with('/'))
+                })
+        }
+
+        fn excludes(&self, name: &str) -> bool {
+            self.exclude.iter().any(|regex| regex.is_match(name))
+        }
+    }
+
+    /// Filter `table` by applying [`FILTER_ENV_VAR_NAME`], and the file
+    /// [`FILTER_CONFIG_ENV_VAR_NAME`] points to with the filter-by-config
+    /// feature. `zone_tab` holds the zones of each country, to keep the
+    /// countries the file lists.
+    pub(crate) fn maybe_filter_timezone_table(table: &mut Table, zone_tab: &[(String, String)]) {
+        #[cfg(feature = "filter-by-config")]
+        if let Some(path) = get_filter_config_path() {
+            let mut filter = read_filter_config(path, table, zone_tab);
+            // The filter regex keeps whatever it matches as well.
+            if let Some(filter_regex) = get_filter_regex() {
+                filter.include.push(filter_regex);
+            }
+            filter_timezone_table_by_config(table, &filter);
+            return;
+        }
+        #[cfg(not(feature = "filter-by-config"))]
+        let _ = zone_tab;
         if let Some(filter_regex) = get_filter_regex() {
             filter_timezone_table(table, filter_regex);
         }
@@ -408,6 +1213,108 @@ mod filter {
         }
     }
 
+    /// Checks the `CHRONO_TZ_FILTER_CONFIG` environment variable, and tells
+    /// Cargo to build again when the file it points to changes.
+    #[cfg(feature = "filter-by-config")]
+    fn get_filter_config_path() -> Option<PathBuf> {
+        let path = PathBuf::from(env::var_os(FILTER_CONFIG_ENV_VAR_NAME)?);
+        if path.as_os_str().is_empty() {
+            return None;
+        }
+        println!("cargo:rerun-if-changed={}", path.display());
+        Some(path)
+    }
+
+    /// Reads a filter config file, such as:
+    ///
+    /// ```toml
+    /// include = ["Europe/London", "America/Argentina/.*"]
+    /// countries = ["US", "DE"]
+    /// regions = ["Australia"]
+    /// exclude = ["America/Indiana/.*"]
+    /// ```
+    ///
+    /// Each of the lists can be left out. Panics if the file can't be read or
+    /// holds anything else, or if it lists a country or region that has no
+    /// zones, as we want to fail the build if that happens.
+    #[cfg(feature = "filter-by-config")]
+    fn read_filter_config(path: PathBuf, table: &Table, zone_tab: &[(String, String)]) -> Filter {
+        let text = std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("cannot read {}: {e}", path.display()));
+        let config = text.parse::<toml::Table>().unwrap_or_else(|e| {
+            panic!(
+                "The filter config {} is not valid TOML: {e}",
+                path.display()
+            )
+        });
+
+        let mut filter = Filter::default();
+        for (key, value) in &config {
+            let values = value
+                .as_array()
+                .and_then(|values| {
+                    values
+                        .iter()
+                        .map(|v| v.as_str())
+                        .collect::<Option<Vec<_>>>()
+                })
+                .unwrap_or_else(|| {
+                    panic!(
+                        "The value of '{key}' in the filter config {} is not a list of strings",
+                        path.display()
+                    )
+                });
+            // The patterns have to match whole names, so that `EST` doesn't
+            // keep `EST5EDT` too.
+            let pattern = |pattern: &str| {
+                Regex::new(&format!("^(?:{pattern})$")).unwrap_or_else(|err| {
+                    panic!(
+                        "The pattern '{pattern}' in the filter config {} is not a valid regex, err={err}",
+                        path.display()
+                    )
+                })
+            };
+            match key.as_str() {
+                "include" => filter.include.extend(values.into_iter().map(pattern)),
+                "exclude" => filter.exclude.extend(values.into_iter().map(pattern)),
+                "countries" => {
+                    for code in values {
+                        let zones = zone_tab
+                            .iter()
+                            .filter(|(country, _)| country.eq_ignore_ascii_case(code))
+                            .map(|(_, zone)| zone.clone())
+                            .collect::<Vec<_>>();
+                        if zones.is_empty() {
+                            panic!(
+                                "The country '{code}' in the filter config {} has no zones in zone.tab",
+                                path.display()
+                            );
+                        }
+                        filter.zones.extend(zones);
+                    }
+                }
+                "regions" => {
+                    for region in values {
+                        let region = region.trim_end_matches('/').replace("::", "/");
+                        let mut names = table.zonesets.keys().chain(table.links.keys());
+                        if !names.any(|name| name.starts_with(&format!("{region}/"))) {
+                            panic!(
+                                "The region '{region}' in the filter config {} has no zones",
+                                path.display()
+                            );
+                        }
+                        filter.regions.push(region);
+                    }
+                }
+                _ => panic!(
+                    "The filter config {} has '{key}', which is not one of include, exclude, countries or regions",
+                    path.display()
+                ),
+            }
+        }
+        filter
+    }
+
     /// Insert a new name in the list of names to keep. If the name has 3
     /// parts, then also insert the 2-part prefix. If we don't do this we will lose
     /// half of Indiana in `directory.rs`. But we *don't* want to keep one-part names,
@@ -472,12 +1379,64 @@ mod filter {
             .zonesets
             .retain(|k, _| filter_regex.is_match(k) || keep.iter().any(|s| k.starts_with(s)));
     }
+
+    /// Filter `table` by applying `filter`, read from the filter config.
+    #[cfg(feature = "filter-by-config")]
+    fn filter_timezone_table_by_config(table: &mut Table, filter: &Filter) {
+        let always = |name: &str| name == "GMT" || name == "UTC";
+        let excluded = |name: &str| filter.excludes(name) && !always(name);
+
+        // The links to each zone.
+        let mut links = BTreeMap::<&str, Vec<&str>>::new();
+        for (link, zone) in &table.links {
+            links.entry(zone.as_str()).or_default().push(link.as_str());
+        }
+
+        // Compute the transitive closure of things to keep: the links to a
+        // zone that is kept are kept too, and so is the zone a kept link
+        // points to, unless they're excluded. Doing this, instead of just
+        // filtering `zonesets` and `links` by the names, keeps the aliases of
+        // a zone together. The modules the names are in follow from them.
+        let names = table.zonesets.keys().chain(table.links.keys());
+        let mut keep = names
+            .map(String::as_str)
+            .filter(|&name| {
+                always(name) || ((filter.keeps_all() || filter.includes(name)) && !excluded(name))
+            })
+            .collect::<HashSet<_>>();
+        let mut queue = keep.iter().copied().collect::<Vec<_>>();
+        while let Some(name) = queue.pop() {
+            let zone = table.links.get(name).map(String::as_str);
+            let aliases = links.get(name).into_iter().flatten().copied();
+            for other in zone.into_iter().chain(aliases) {
+                if (always(name) || !excluded(other)) && keep.insert(other) {
+                    queue.push(other);
+                }
+            }
+        }
+
+        // Actually do the filtering. A link to a zone that is left out can't
+        // be kept either.
+        let keep = keep.into_iter().map(str::to_owned).collect::<HashSet<_>>();
+        table
+            .zonesets
+            .retain(|name, _| keep.contains(name.as_str()));
+        let zonesets = &table.zonesets;
+        table.links.retain(|link, zone| {
+            keep.contains(link.as_str()) && (zonesets.contains_key(zone) || keep.contains(zone))
+        });
+    }
 }
 
-fn detect_iana_db_version() -> String {
-    let root = env::var("CARGO_MANIFEST_DIR").expect("no Cargo build context");
-    let path = Path::new(&root).join(Path::new("tz/NEWS"));
-    let file = File::open(path).expect("failed to open file");
+// Release tarballs of the tz database come with a `version` file, while its
+// repository only has the `NEWS` file, which starts with the latest release.
+fn detect_iana_db_version(tz_dir: &Path) -> String {
+    if let Ok(version) = std::fs::read_to_string(tz_dir.join("version")) {
+        return version.trim().to_owned();
+    }
+
+    let path = tz_dir.join("NEWS");
+    let file = File::open(&path).unwrap_or_else(|e| panic!("cannot open {}: {e}", path.display()));
 
     let mut lines = BufReader::new(file).lines();
     while let Some(Ok(line)) = lines.next() {
@@ -495,33 +1454,462 @@ fn detect_iana_db_version() -> String {
     unreachable!("no version found")
 }
 
-pub fn main(dir: &Path, _filter: bool, _uncased: bool) {
-    let mut table = TableBuilder::new();
+/// Reads the tzdata directory to build from [`TZDATA_DIR_ENV_VAR_NAME`], if it
+/// is set. Panics if it's not a directory, as we want to fail the build if
+/// that happens.
+fn get_tzdata_dir() -> Option<PathBuf> {
+    let dir = PathBuf::from(env::var_os(TZDATA_DIR_ENV_VAR_NAME)?);
+    if dir.as_os_str().is_empty() {
+        return None;
+    }
+    if !dir.is_dir() {
+        panic!(
+            "The value '{}' for environment variable {TZDATA_DIR_ENV_VAR_NAME} is not a directory",
+            dir.display()
+        );
+    }
+    Some(dir)
+}
 
-    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| String::new()));
+/// Reads the form of the data to build from [`DATA_FORM_ENV_VAR_NAME`], which
+/// is the main form if it's not set. Panics if it's set to anything else than
+/// the name of a form, as we want to fail the build if that happens.
+fn get_data_form() -> DataForm {
+    match env::var(DATA_FORM_ENV_VAR_NAME) {
+        Ok(val) if val.trim().is_empty() => DataForm::Main,
+        Ok(val) => val.trim().parse().unwrap_or_else(|err| {
+            panic!("The value '{val:?}' for environment variable {DATA_FORM_ENV_VAR_NAME} is not valid, err={err}")
+        }),
+        Err(env::VarError::NotPresent) => DataForm::Main,
+        Err(env::VarError::NotUnicode(s)) => panic!(
+            "The value '{s:?}' for environment variable {DATA_FORM_ENV_VAR_NAME} is not valid Unicode"
+        ),
+    }
+}
+
+/// The years to keep the transitions of, as the timestamps of the start of
+/// the first and of the year after the last, along with what the offsets are
+/// outside of them.
+///
+/// This works like the `-r` option of zic.
+struct YearRange {
+    start: Option<i64>,
+    end: Option<i64>,
+    edges: Edges,
+}
+
+/// What the offsets are outside of a [`YearRange`].
+#[derive(Clone, Copy)]
+enum Edges {
+    /// The offsets in effect at the edges of the range stay in effect
+    /// beyond them.
+    Clamp,
+    /// The offsets beyond the edges of the range are unspecified, which the
+    /// tz database writes as `-00` with no offset from UTC.
+    Unspecified,
+}
+
+impl YearRange {
+    /// Drops the transitions outside of the range, and pins the offsets
+    /// beyond its edges where that drops any. The recurring rules are
+    /// dropped too if the range has an end.
+    fn trim(&self, timespans: &mut FixedTimespanSet, footer: &mut Option<PosixTz>) {
+        let unspecified = || FixedTimespan {
+            utc_offset: 0,
+            dst_offset: 0,
+            name: "-00".to_owned(),
+        };
+
+        if let Some(start) = self.start {
+            let before = timespans
+                .rest
+                .iter()
+                .take_while(|&&(at, _)| at < start)
+                .count();
+            let dropped = timespans.rest.drain(..before).next_back();
+            if let Some((_, at_start)) = dropped {
+                // A placeholder for the times nobody lived there says nothing
+                // about the offsets before the range, so clamping keeps the
+                // zone's own first span then.
+                let before_start = match self.edges {
+                    Edges::Clamp if at_start == unspecified() => timespans.first.clone(),
+                    Edges::Clamp => at_start.clone(),
+                    Edges::Unspecified => unspecified(),
+                };
+                if before_start != at_start
+                    && timespans.rest.first().map_or(true, |&(at, _)| at > start)
+                {
+                    timespans.rest.insert(0, (start, at_start));
+                }
+                timespans.first = before_start;
+            }
+        }
+
+        if let Some(end) = self.end {
+            let kept = timespans
+                .rest
+                .iter()
+                .take_while(|&&(at, _)| at < end)
+                .count();
+            let recurring = footer.as_ref().map_or(false, |footer| footer.dst.is_some());
+            if kept < timespans.rest.len() || recurring {
+                timespans.rest.truncate(kept);
+                *footer = None;
+                if let Edges::Unspecified = self.edges {
+                    timespans.rest.push((end, unspecified()));
+                }
+            }
+        }
+    }
+}
+
+/// Reads the year range to keep the transitions of from
+/// [`YEAR_RANGE_ENV_VAR_NAME`], and what the offsets are outside of it from
+/// [`YEAR_RANGE_EDGES_ENV_VAR_NAME`]. Panics if either is set to something
+/// that isn't valid, as we want to fail the build if that happens.
+fn get_year_range() -> Option<YearRange> {
+    let range = match env::var(YEAR_RANGE_ENV_VAR_NAME) {
+        Ok(val) if val.trim().is_empty() => return None,
+        Ok(val) => val,
+        Err(env::VarError::NotPresent) => return None,
+        Err(env::VarError::NotUnicode(s)) => panic!(
+            "The value '{s:?}' for environment variable {YEAR_RANGE_ENV_VAR_NAME} is not valid Unicode"
+        ),
+    };
+    let (start, end) = parse_year_range(range.trim()).unwrap_or_else(|| {
+        panic!(
+            "The value '{range}' for environment variable {YEAR_RANGE_ENV_VAR_NAME} is not a range of years such as 1970..2060"
+        )
+    });
+    // The transitions of the tz data end there, so the recurring rules
+    // would have to be expanded to go any further.
+    if end.map_or(false, |end| end > YEARS.end) {
+        panic!(
+            "The range '{range}' for environment variable {YEAR_RANGE_ENV_VAR_NAME} can't end after {}; leave the end out to keep following the current rules",
+            YEARS.end,
+        );
+    }
+
+    let edges = match env::var(YEAR_RANGE_EDGES_ENV_VAR_NAME) {
+        Ok(val) => match val.trim() {
+            "" | "clamp" => Edges::Clamp,
+            "-00" => Edges::Unspecified,
+            _ => panic!(
+                "The value '{val}' for environment variable {YEAR_RANGE_EDGES_ENV_VAR_NAME} is not one of clamp or -00"
+            ),
+        },
+        Err(env::VarError::NotPresent) => Edges::Clamp,
+        Err(env::VarError::NotUnicode(s)) => panic!(
+            "The value '{s:?}' for environment variable {YEAR_RANGE_EDGES_ENV_VAR_NAME} is not valid Unicode"
+        ),
+    };
+
+    let timestamp = |year| ChangeTime::UntilYear(Year::Number(year)).to_timestamp(0, 0);
+    Some(YearRange {
+        start: start.map(timestamp),
+        end: end.map(timestamp),
+        edges,
+    })
+}
+
+/// Parses a range of years like Rust writes them, such as `1970..2060`,
+/// `1970..=2059`, `1970..` or `..2060`, into the first year and the one
+/// after the last.
+fn parse_year_range(range: &str) -> Option<(Option<i64>, Option<i64>)> {
+    let (start, end) = range.split_once("..")?;
+    let year = |year: &str| match year.trim() {
+        "" => Some(None),
+        year => year.parse::<i64>().ok().map(Some),
+    };
+    let start = year(start)?;
+    let end = match end.strip_prefix('=') {
+        Some(end) => Some(year(end)?? + 1),
+        None => year(end)?,
+    };
+    match (start, end) {
+        (Some(start), Some(end)) if start >= end => None,
+        _ => Some((start, end)),
+    }
+}
+
+// Reads the tz source files in the given form, noting the names of the links
+// of the `backward` file. Errors in them fail the build with a diagnostic
+// that points at the line.
+fn read_source_files(tz_dir: &Path, form: DataForm, backward: &mut BTreeSet<String>) -> Table {
+    let mut table = TableBuilder::new();
     for fname in FILES {
-        let path = root.join(format!("tz/{fname}"));
+        let path = tz_dir.join(fname);
         let file =
             File::open(&path).unwrap_or_else(|e| panic!("cannot open {}: {e}", path.display()));
+        let mut converter = Converter::new(form);
+        let mut text = String::new();
         for line in BufReader::new(file).lines() {
-            let line = strip_comments(line.unwrap());
-            table.add_line(Line::new(&line).unwrap()).unwrap();
+            let line = converter.convert(&line.unwrap()).into_owned();
+            if let (&"backward", Ok(Line::Link(link))) = (fname, Line::new(&line)) {
+                backward.insert(link.new.to_owned());
+            }
+            text.push_str(&line);
+            text.push('\n');
+        }
+        table
+            .add_file(&path.display().to_string(), text.as_bytes())
+            .unwrap_or_else(|e| panic!("\n{e}"));
+    }
+    table.build()
+}
+
+// Notes the names of the links of the `backward` file at `path`, if there is
+// one, returning whether there was.
+fn read_backward_links(path: &Path, backward: &mut BTreeSet<String>) -> bool {
+    let Ok(file) = File::open(path) else {
+        return false;
+    };
+    for line in BufReader::new(file).lines() {
+        if let Ok(Line::Link(link)) = Line::new(&line.unwrap()) {
+            backward.insert(link.new.to_owned());
+        }
+    }
+    true
+}
+
+// Releases of the tz database come with the `leapseconds` file, which its
+// repository generates from `leap-seconds.list`.
+fn read_leap_seconds(tz_dir: &Path, table: &mut Table) {
+    let path = tz_dir.join("leapseconds");
+    let Ok(file) = File::open(&path) else {
+        read_leap_seconds_list(&tz_dir.join("leap-seconds.list"), table);
+        return;
+    };
+
+    let mut text = String::new();
+    for line in BufReader::new(file).lines() {
+        let line = line.unwrap();
+        // The `Expires` line is commented out, as zic only supports it
+        // since release 2020a.
+        text.push_str(match line.strip_prefix('#') {
+            Some(expires) if expires.starts_with("Expires") => expires,
+            _ => &line,
+        });
+        text.push('\n');
+    }
+    let mut leap_table = TableBuilder::new();
+    leap_table
+        .add_file(&path.display().to_string(), text.as_bytes())
+        .unwrap_or_else(|e| panic!("\n{e}"));
+    let leap_table = leap_table.build();
+    table.leap_seconds = leap_table.leap_seconds;
+    table.leap_seconds_expiry = leap_table.leap_seconds_expiry;
+}
+
+// Reads the leap seconds from the list published by the IERS, which gives the
+// time of each leap second as the number of seconds since 1900, followed by
+// the difference between TAI and UTC from then on.
+fn read_leap_seconds_list(path: &Path, table: &mut Table) {
+    const SECONDS_FROM_1900_TO_1970: i64 = 2_208_988_800;
+
+    let file = File::open(path).unwrap_or_else(|e| panic!("cannot open {}: {e}", path.display()));
+    let mut previous = None;
+    for line in BufReader::new(file).lines() {
+        let line = line.unwrap();
+        if let Some(expiry) = line.strip_prefix("#@") {
+            let expiry = expiry.trim().parse::<i64>().unwrap();
+            table.leap_seconds_expiry = Some(expiry - SECONDS_FROM_1900_TO_1970);
+            continue;
+        }
+
+        let line = strip_comments(line);
+        let mut columns = line.split_ascii_whitespace();
+        let (Some(time), Some(tai_offset)) = (columns.next(), columns.next()) else {
+            continue;
+        };
+        let time = time.parse::<i64>().unwrap() - SECONDS_FROM_1900_TO_1970;
+        let tai_offset = tai_offset.parse::<i64>().unwrap();
+        // The first entry is the start of 1972, when UTC was already 10
+        // seconds behind TAI.
+        if let Some(previous) = previous.replace(tai_offset) {
+            table.leap_seconds.push(LeapSecond {
+                timestamp: time,
+                correction: tai_offset - previous,
+                rolling: false,
+            });
         }
     }
+}
+
+/// What to build, which `chrono-tz` sets from its features
+///
+/// Each option reads the environment variable it goes with, so that leaving
+/// one off ignores the variable.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BuildOptions {
+    /// Filter the zones by `CHRONO_TZ_TIMEZONE_FILTER`, which needs the
+    /// `filter-by-regex` feature.
+    pub filter: bool,
+    /// Make parsing zone names case-insensitive, which needs the
+    /// `case-insensitive` feature.
+    pub uncased: bool,
+    /// Leave out the links from the `backward` file.
+    pub exclude_backward_links: bool,
+    /// Build from the form of the data [`DATA_FORM_ENV_VAR_NAME`] names.
+    pub data_form: bool,
+    /// Build from the directory [`TZDATA_DIR_ENV_VAR_NAME`] points to.
+    pub tzdata_dir: bool,
+    /// Trim the transitions to the years [`YEAR_RANGE_ENV_VAR_NAME`] holds.
+    pub year_range: bool,
+}
+
+/// Writes `timezones.rs` and `directory.rs` into `dir`, with the filtering
+/// and case-insensitivity given, and none of the other options.
+pub fn main(dir: &Path, filter: bool, uncased: bool) {
+    build(
+        dir,
+        &BuildOptions {
+            filter,
+            uncased,
+            ..BuildOptions::default()
+        },
+    );
+}
+
+/// Writes `timezones.rs` and `directory.rs` into `dir`.
+pub fn build(dir: &Path, options: &BuildOptions) {
+    let mut backward = BTreeSet::new();
+    let form = match options.data_form {
+        true => get_data_form(),
+        false => DataForm::Main,
+    };
+    let year_range = match options.year_range {
+        true => get_year_range(),
+        false => None,
+    };
 
-    #[allow(unused_mut)]
-    let mut table = table.build();
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| String::new()));
+    let tz_dir = match options.tzdata_dir {
+        true => get_tzdata_dir().unwrap_or_else(|| root.join("tz")),
+        false => root.join("tz"),
+    };
+    // Systems that ship the tz database often only have its `tzdata.zi`
+    // file, which holds the data of all the source files in the main form,
+    // but doesn't tell which links come from `backward`.
+    let tzdata_zi = tz_dir.join("tzdata.zi");
+    let mut table = if !tz_dir.join(FILES[0]).exists() && tzdata_zi.exists() {
+        if form != DataForm::Main {
+            panic!(
+                "{} only holds the main form of the data, so it cannot be built in the {form} form",
+                tzdata_zi.display()
+            );
+        }
+        if !read_backward_links(&tz_dir.join("backward"), &mut backward)
+            && options.exclude_backward_links
+        {
+            panic!(
+                "{} doesn't tell which links come from `backward`, and there is no `backward` \
+                 file next to it, so the `exclude-backward-links` feature cannot be used",
+                tzdata_zi.display()
+            );
+        }
+        let file = File::open(&tzdata_zi)
+            .unwrap_or_else(|e| panic!("cannot open {}: {e}", tzdata_zi.display()));
+        Table::from_tzdata_zi(BufReader::new(file)).unwrap_or_else(|e| panic!("\n{e}"))
+    } else {
+        read_source_files(&tz_dir, form, &mut backward)
+    };
+    read_leap_seconds(&tz_dir, &mut table);
+    let mut zone_tables = read_zone_tables(&tz_dir);
     #[cfg(feature = "filter-by-regex")]
-    if _filter {
-        filter::maybe_filter_timezone_table(&mut table);
+    if options.filter {
+        filter::maybe_filter_timezone_table(&mut table, &zone_tables.zone_tab);
+    }
+    let backward = BackwardLinks::new(&mut table, backward, options.exclude_backward_links);
+
+    // The zone tables still use some of the names from `backward`, and so
+    // does CLDR, which `write_windows_zones` takes care of.
+    let windows_zones = read_windows_zones(&root.join("cldr/windowsZones.xml"));
+    for (_, zone) in &mut zone_tables.zone_tab {
+        *zone = backward.resolve(zone).to_owned();
+    }
+    for (alias, target) in backward.excluded() {
+        if let Some(point) = zone_tables.coordinates.remove(alias) {
+            zone_tables
+                .coordinates
+                .entry(target.clone())
+                .or_insert(point);
+        }
     }
 
     let timezone_path = dir.join("timezones.rs");
     let mut timezone_file = File::create(timezone_path).unwrap();
-    write_timezone_file(&mut timezone_file, &table, _uncased).unwrap();
+    write_timezone_file(
+        &mut timezone_file,
+        &table,
+        &windows_zones,
+        &zone_tables,
+        &backward,
+        options.uncased,
+        year_range.as_ref(),
+    )
+    .unwrap();
 
     let directory_path = dir.join("directory.rs");
     let mut directory_file = File::create(directory_path).unwrap();
-    let version = detect_iana_db_version();
-    write_directory_file(&mut directory_file, &table, &version).unwrap();
+    let version = match table.version.take() {
+        Some(version) => version,
+        None => detect_iana_db_version(&tz_dir),
+    };
+    write_directory_file(&mut directory_file, &table, &version, form).unwrap();
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timespans(zone: &str, start: i64, edges: Edges) -> (FixedTimespanSet, FixedTimespanSet) {
+        let tz_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../chrono-tz/tz");
+        let table = read_source_files(&tz_dir, DataForm::Main, &mut BTreeSet::new());
+        let timestamp = |year| ChangeTime::UntilYear(Year::Number(year)).to_timestamp(0, 0);
+        let range = YearRange {
+            start: Some(timestamp(start)),
+            end: None,
+            edges,
+        };
+        let untrimmed = table.timespans(zone).unwrap();
+        let mut trimmed = untrimmed.clone();
+        range.trim(&mut trimmed, &mut table.posix_tz(zone));
+        (untrimmed, trimmed)
+    }
+
+    #[test]
+    fn trim_before_first_transition() {
+        // Both stations came after 1960, with no one there before.
+        for zone in ["Antarctica/Troll", "Antarctica/Casey"] {
+            for edges in [Edges::Clamp, Edges::Unspecified] {
+                let (untrimmed, trimmed) = timespans(zone, 1960, edges);
+                assert_eq!(trimmed.first.name, "-00");
+                assert_eq!(trimmed.first, untrimmed.first, "{zone}");
+                assert_eq!(trimmed.rest, untrimmed.rest, "{zone}");
+            }
+        }
+    }
+
+    #[test]
+    fn trim_after_first_transition() {
+        let (_, trimmed) = timespans("Antarctica/Casey", 1970, Edges::Clamp);
+        assert_eq!(trimmed.first.name, "+08");
+        assert_eq!(trimmed.rest[0].1.name, "+11");
+
+        let (_, trimmed) = timespans("Antarctica/Casey", 1970, Edges::Unspecified);
+        assert_eq!(trimmed.first.name, "-00");
+        assert_eq!(trimmed.rest[0].0, 0);
+        assert_eq!(trimmed.rest[0].1.name, "+08");
+    }
+
+    #[test]
+    fn trim_within_placeholder() {
+        // Macquarie Island had no one on it between 1919 and 1948.
+        for edges in [Edges::Clamp, Edges::Unspecified] {
+            let (untrimmed, trimmed) = timespans("Antarctica/Macquarie", 1930, edges);
+            assert_eq!(trimmed.first, untrimmed.first);
+            assert_eq!(trimmed.rest[0].1.name, "AEST");
+            assert_eq!(trimmed.rest[0].0, -687052800);
+        }
+    }
 }
@@ -1,16 +1,67 @@
-#[cfg(any(feature = "filter-by-regex", feature = "case-insensitive"))]
+#[cfg(any(
+    feature = "filter-by-regex",
+    feature = "case-insensitive",
+    feature = "exclude-backward-links",
+    feature = "data-form",
+    feature = "custom-tzdata",
+    feature = "year-range"
+))]
 use std::{env, path::Path};
 
+#[cfg(feature = "data-form")]
+use chrono_tz_build::DATA_FORM_ENV_VAR_NAME;
+#[cfg(feature = "filter-by-config")]
+use chrono_tz_build::FILTER_CONFIG_ENV_VAR_NAME;
 #[cfg(feature = "filter-by-regex")]
 use chrono_tz_build::FILTER_ENV_VAR_NAME;
+#[cfg(feature = "custom-tzdata")]
+use chrono_tz_build::TZDATA_DIR_ENV_VAR_NAME;
+#[cfg(feature = "year-range")]
+use chrono_tz_build::{YEAR_RANGE_EDGES_ENV_VAR_NAME, YEAR_RANGE_ENV_VAR_NAME};
 
 fn main() {
     #[cfg(feature = "filter-by-regex")]
     println!("cargo:rerun-if-env-changed={FILTER_ENV_VAR_NAME}");
-    #[cfg(any(feature = "filter-by-regex", feature = "case-insensitive"))]
-    chrono_tz_build::main(
-        Path::new(&env::var("OUT_DIR").unwrap()),
-        cfg!(feature = "filter-by-regex"),
-        cfg!(feature = "case-insensitive"),
-    );
+    #[cfg(feature = "filter-by-config")]
+    println!("cargo:rerun-if-env-changed={FILTER_CONFIG_ENV_VAR_NAME}");
+    #[cfg(feature = "data-form")]
+    println!("cargo:rerun-if-env-changed={DATA_FORM_ENV_VAR_NAME}");
+    // The tests that rely on the bundled data check this.
+    println!("cargo:rustc-check-cfg=cfg(chrono_tz_custom_tzdata)");
+    #[cfg(feature = "custom-tzdata")]
+    {
+        println!("cargo:rerun-if-env-changed={TZDATA_DIR_ENV_VAR_NAME}");
+        if let Some(dir) = env::var_os(TZDATA_DIR_ENV_VAR_NAME).filter(|dir| !dir.is_empty()) {
+            println!("cargo:rerun-if-changed={}", Path::new(&dir).display());
+            println!("cargo:rustc-cfg=chrono_tz_custom_tzdata");
+        }
+    }
+    #[cfg(feature = "year-range")]
+    {
+        println!("cargo:rerun-if-env-changed={YEAR_RANGE_ENV_VAR_NAME}");
+        println!("cargo:rerun-if-env-changed={YEAR_RANGE_EDGES_ENV_VAR_NAME}");
+    }
+    // The library uses the code generated here instead of the prebuilt one
+    // whenever one of these features is enabled.
+    println!("cargo:rustc-check-cfg=cfg(chrono_tz_codegen)");
+    #[cfg(any(
+        feature = "filter-by-regex",
+        feature = "case-insensitive",
+        feature = "exclude-backward-links",
+        feature = "data-form",
+        feature = "custom-tzdata",
+        feature = "year-range"
+    ))]
+    {
+        println!("cargo:rustc-cfg=chrono_tz_codegen");
+        let options = chrono_tz_build::BuildOptions {
+            filter: cfg!(feature = "filter-by-regex"),
+            uncased: cfg!(feature = "case-insensitive"),
+            exclude_backward_links: cfg!(feature = "exclude-backward-links"),
+            data_form: cfg!(feature = "data-form"),
+            tzdata_dir: cfg!(feature = "custom-tzdata"),
+            year_range: cfg!(feature = "year-range"),
+        };
+        chrono_tz_build::build(Path::new(&env::var("OUT_DIR").unwrap()), &options);
+    }
 }
@@ -0,0 +1,123 @@
+#[cfg(feature = "std")]
+use crate::timezones::ZONES_BY_LATITUDE;
+use crate::timezones::{Tz, ZONE_COORDINATES};
+
+/// A point on Earth, in degrees
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Coordinates {
+    /// The latitude, north of the equator when positive and south of it when negative
+    pub latitude: f64,
+    /// The longitude, east of the prime meridian when positive and west of it when negative
+    pub longitude: f64,
+}
+
+impl Coordinates {
+    fn from_arcseconds(latitude: i32, longitude: i32) -> Self {
+        Coordinates {
+            latitude: f64::from(latitude) / 3600.0,
+            longitude: f64::from(longitude) / 3600.0,
+        }
+    }
+}
+
+impl Tz {
+    /// Returns the location of the principal city of this timezone
+    ///
+    /// These are the coordinates the tz database's `zone1970.tab` and `zone.tab` give, such as
+    /// those of Paris for `Europe/Paris`. Links without an entry of their own share the
+    /// coordinates of the zone they point to, and zones that aren't tied to a place, such as
+    /// `UTC` and `Etc/GMT+5`, have none.
+    ///
+    /// ```
+    /// use chrono_tz::Tz;
+    ///
+    /// // 48°52′N 2°20′E
+    /// let paris = Tz::Europe__Paris.coordinates().unwrap();
+    /// assert!((paris.latitude - 48.8667).abs() < 1e-4);
+    /// assert!((paris.longitude - 2.3333).abs() < 1e-4);
+    /// assert_eq!(Tz::UTC.coordinates(), None);
+    /// ```
+    pub fn coordinates(&self) -> Option<Coordinates> {
+        let (latitude, longitude) = ZONE_COORDINATES.get(self.name())?;
+        Some(Coordinates::from_arcseconds(*latitude, *longitude))
+    }
+}
+
+/// Returns the timezone whose principal city is closest to a location
+///
+/// The location is given in degrees, as for [`Coordinates`]. This only compares the distance to
+/// the cities returned by [`Tz::coordinates`] rather than looking at the actual borders of each
+/// zone, so it can pick a zone across a nearby border: Lyon is closer to Monaco than to Paris,
+/// for instance. It returns `None` if the latitude is out of range, or if either value isn't
+/// finite.
+///
+/// ```
+/// use chrono_tz::{nearest_zone, Tz};
+///
+/// // Orléans, France
+/// assert_eq!(nearest_zone(47.90, 1.90), Some(Tz::Europe__Paris));
+/// // Geelong, Australia
+/// assert_eq!(nearest_zone(-38.15, 144.36), Some(Tz::Australia__Melbourne));
+/// ```
+#[cfg(feature = "std")]
+pub fn nearest_zone(latitude: f64, longitude: f64) -> Option<Tz> {
+    if !(-90.0..=90.0).contains(&latitude) || !longitude.is_finite() {
+        return None;
+    }
+
+    let point = Coordinates {
+        latitude,
+        longitude,
+    };
+    let coordinates = |index: usize| {
+        let (latitude, longitude, tz) = ZONES_BY_LATITUDE[index];
+        (Coordinates::from_arcseconds(latitude, longitude), tz)
+    };
+    // The difference in latitude alone is a lower bound of the distance, so
+    // searching outward from the given latitude can stop as soon as it exceeds
+    // the distance to the closest zone found.
+    let gap = |index: usize| {
+        (coordinates(index).0.latitude - latitude)
+            .abs()
+            .to_radians()
+    };
+
+    let start = ZONES_BY_LATITUDE
+        .partition_point(|(zone_latitude, _, _)| f64::from(*zone_latitude) / 3600.0 < latitude);
+    let (mut below, mut above) = (start, start);
+    let mut nearest: Option<(f64, Tz)> = None;
+    loop {
+        let index = match (below.checked_sub(1), above < ZONES_BY_LATITUDE.len()) {
+            (Some(south), true) if gap(south) < gap(above) => south,
+            (Some(south), false) => south,
+            (_, true) => above,
+            (None, false) => break,
+        };
+        if nearest.map_or(false, |(distance, _)| gap(index) >= distance) {
+            break;
+        }
+
+        match index < start {
+            true => below = index,
+            false => above = index + 1,
+        }
+        let (zone, tz) = coordinates(index);
+        let distance = central_angle(point, zone);
+        if nearest.map_or(true, |(nearest, _)| distance < nearest) {
+            nearest = Some((distance, tz));
+        }
+    }
+
+    nearest.map(|(_, tz)| tz)
+}
+
+/// The angle between two points as seen from the center of the Earth, in radians
+#[cfg(feature = "std")]
+fn central_angle(a: Coordinates, b: Coordinates) -> f64 {
+    let (a_latitude, b_latitude) = (a.latitude.to_radians(), b.latitude.to_radians());
+    let latitude = (b_latitude - a_latitude) / 2.0;
+    let longitude = (b.longitude - a.longitude).to_radians() / 2.0;
+    let haversine =
+        latitude.sin().powi(2) + a_latitude.cos() * b_latitude.cos() * longitude.sin().powi(2);
+    2.0 * haversine.sqrt().min(1.0).asin()
+}
@@ -0,0 +1,72 @@
+use crate::timezones::{Tz, COUNTRY_NAMES, COUNTRY_ZONES, ZONE_COUNTRIES};
+
+/// Returns the zones in use in a country, given its ISO 3166 code
+///
+/// The zones come in the order of the tz database's `zone.tab`, which mostly goes from east to
+/// west, followed by any zone `zone1970.tab` adds for the country. Codes are matched without
+/// regard to case, and an unknown code gives an empty list.
+///
+/// ```
+/// use chrono_tz::{zones_for_country, Tz};
+///
+/// let zones = zones_for_country("BR");
+/// assert_eq!(zones[0], Tz::America__Noronha);
+/// assert!(zones.contains(&Tz::America__Sao_Paulo));
+/// assert!(zones_for_country("XX").is_empty());
+/// ```
+pub fn zones_for_country(code: &str) -> &'static [Tz] {
+    let code = uppercase(code);
+    let code = code.as_ref().map_or("", |code| code.as_str());
+    COUNTRY_ZONES.get(code).copied().unwrap_or_default()
+}
+
+/// Returns the English name of a country, given its ISO 3166 code
+///
+/// ```
+/// use chrono_tz::country_name;
+///
+/// assert_eq!(country_name("BR"), Some("Brazil"));
+/// assert_eq!(country_name("XX"), None);
+/// ```
+pub fn country_name(code: &str) -> Option<&'static str> {
+    COUNTRY_NAMES.get(uppercase(code)?.as_str()).copied()
+}
+
+impl Tz {
+    /// Returns the ISO 3166 codes of the countries this timezone is used in
+    ///
+    /// This is the inverse of [`zones_for_country`]: a zone covering several countries is only
+    /// listed for those that don't have a link of their own to it, such as `Europe/Oslo` for
+    /// Norway. Links therefore return their own country, and `Etc/` zones none at all.
+    ///
+    /// ```
+    /// use chrono_tz::Tz;
+    ///
+    /// assert_eq!(Tz::America__Sao_Paulo.countries(), ["BR"]);
+    /// assert!(Tz::Europe__Berlin.countries().contains(&"DE"));
+    /// assert!(Tz::Etc__UTC.countries().is_empty());
+    /// ```
+    pub fn countries(&self) -> &'static [&'static str] {
+        ZONE_COUNTRIES.get(self.name()).copied().unwrap_or_default()
+    }
+}
+
+/// A country code with its letters uppercased, as they are in the tables
+struct CountryCode([u8; 2]);
+
+impl CountryCode {
+    fn as_str(&self) -> &str {
+        // Only ASCII letters make it in here.
+        core::str::from_utf8(&self.0).unwrap_or_default()
+    }
+}
+
+fn uppercase(code: &str) -> Option<CountryCode> {
+    match *code.as_bytes() {
+        [a, b] if a.is_ascii_alphabetic() && b.is_ascii_alphabetic() => Some(CountryCode([
+            a.to_ascii_uppercase(),
+            b.to_ascii_uppercase(),
+        ])),
+        _ => None,
+    }
+}
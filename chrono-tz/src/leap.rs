@@ -0,0 +1,205 @@
+use core::fmt::{self, Display, Formatter};
+use core::str::FromStr;
+
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+
+use crate::timezones::{ParseError, Tz, LEAP_SECONDS, LEAP_SECONDS_EXPIRY};
+
+/// The number of seconds TAI was ahead of UTC at the start of 1972, when leap seconds began
+const TAI_OFFSET_1972: i32 = 10;
+
+/// The Unix timestamp of the start of 1972
+const START_OF_1972: i64 = 63_072_000;
+
+/// A leap second of the tz database's `leapseconds` file
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LeapSecond {
+    /// The Unix timestamp from which the leap second counts, which is the midnight UTC right
+    /// after it
+    pub timestamp: i64,
+    /// The number of seconds TAI is ahead of UTC from then on
+    pub tai_offset: i32,
+}
+
+/// Returns the leap seconds that were added to UTC since 1972, in order
+///
+/// ```
+/// use chrono_tz::leap_seconds;
+///
+/// let first = leap_seconds()[0];
+/// // 1972-07-01 00:00:00 UTC
+/// assert_eq!(first.timestamp, 78796800);
+/// assert_eq!(first.tai_offset, 11);
+/// ```
+pub fn leap_seconds() -> &'static [LeapSecond] {
+    &LEAP_SECONDS
+}
+
+/// Returns the time until which the list of leap seconds is known to be complete
+///
+/// Leap seconds are announced about six months in advance, so the list can't tell whether any
+/// will be added after this. The conversions in this module still assume that none are.
+pub fn leap_seconds_expiry() -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(LEAP_SECONDS_EXPIRY?, 0)
+}
+
+/// Returns the number of seconds TAI is ahead of UTC at a given time
+///
+/// This is `None` before 1972, when UTC seconds were not yet the same length as TAI ones.
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use chrono_tz::tai_offset;
+///
+/// assert_eq!(tai_offset(&Utc.with_ymd_and_hms(2016, 12, 31, 23, 59, 59).unwrap()), Some(36));
+/// assert_eq!(tai_offset(&Utc.with_ymd_and_hms(2017, 1, 1, 0, 0, 0).unwrap()), Some(37));
+/// assert_eq!(tai_offset(&Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap()), None);
+/// ```
+pub fn tai_offset(utc: &DateTime<Utc>) -> Option<i32> {
+    match utc.timestamp() < START_OF_1972 {
+        true => None,
+        false => Some(TAI_OFFSET_1972 + leap_count(utc.timestamp())),
+    }
+}
+
+/// Converts a time from UTC to TAI
+///
+/// A leap second, which chrono represents as a time with more than a second's worth of
+/// nanoseconds, gets the TAI time between those of the seconds around it. This is `None` before
+/// 1972, like [`tai_offset`].
+///
+/// ```
+/// use chrono::{NaiveDate, TimeZone, Utc};
+/// use chrono_tz::utc_to_tai;
+///
+/// let leap_second = NaiveDate::from_ymd_opt(2016, 12, 31)
+///     .unwrap()
+///     .and_hms_milli_opt(23, 59, 59, 1_500)
+///     .unwrap()
+///     .and_utc();
+/// let tai = NaiveDate::from_ymd_opt(2017, 1, 1)
+///     .unwrap()
+///     .and_hms_milli_opt(0, 0, 36, 500)
+///     .unwrap();
+/// assert_eq!(utc_to_tai(&leap_second), Some(tai));
+/// ```
+pub fn utc_to_tai(utc: &DateTime<Utc>) -> Option<NaiveDateTime> {
+    if utc.timestamp() < START_OF_1972 {
+        return None;
+    }
+    let (secs, nsecs) = count_leap_seconds(utc);
+    let tai = DateTime::from_timestamp(secs.checked_add(i64::from(TAI_OFFSET_1972))?, nsecs)?;
+    Some(tai.naive_utc())
+}
+
+/// Converts a time from TAI to UTC
+///
+/// This is the inverse of [`utc_to_tai`], so it returns `None` before 1972.
+///
+/// ```
+/// use chrono::{NaiveDate, TimeZone, Utc};
+/// use chrono_tz::tai_to_utc;
+///
+/// let tai = NaiveDate::from_ymd_opt(2017, 1, 1).unwrap().and_hms_opt(0, 0, 37).unwrap();
+/// assert_eq!(tai_to_utc(&tai), Some(Utc.with_ymd_and_hms(2017, 1, 1, 0, 0, 0).unwrap()));
+/// ```
+pub fn tai_to_utc(tai: &NaiveDateTime) -> Option<DateTime<Utc>> {
+    let tai = tai.and_utc();
+    let secs = tai.timestamp().checked_sub(i64::from(TAI_OFFSET_1972))?;
+    if secs < START_OF_1972 {
+        return None;
+    }
+    uncount_leap_seconds(secs, tai.timestamp_subsec_nanos())
+}
+
+/// A timezone of the `right/` directory of a compiled tz database, whose timestamps count leap
+/// seconds
+///
+/// Unix timestamps, like those of [`DateTime::timestamp`], pretend that every day lasts exactly
+/// 86400 seconds. The zones in `right/` count the leap seconds too, so that their timestamps are
+/// ahead by the number of leap seconds since 1972. Local times are the same in both, which is why
+/// this wraps a [`Tz`] rather than being a timezone of its own.
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use chrono_tz::{RightTz, Tz};
+///
+/// let right: RightTz = "right/Europe/Paris".parse().unwrap();
+/// assert_eq!(right, RightTz(Tz::Europe__Paris));
+///
+/// let dt = Utc.with_ymd_and_hms(2017, 1, 1, 0, 0, 0).unwrap().with_timezone(&right.0);
+/// assert_eq!(RightTz::timestamp(&dt), dt.timestamp() + 27);
+/// assert_eq!(right.timestamp_opt(dt.timestamp() + 27, 0), Some(dt));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RightTz(pub Tz);
+
+impl RightTz {
+    /// Returns the local time at a number of seconds and nanoseconds since the Unix epoch,
+    /// counting leap seconds
+    ///
+    /// This is `None` if the time is out of range for chrono.
+    pub fn timestamp_opt(&self, secs: i64, nsecs: u32) -> Option<DateTime<Tz>> {
+        Some(uncount_leap_seconds(secs, nsecs)?.with_timezone(&self.0))
+    }
+
+    /// Returns the number of seconds since the Unix epoch at a time, counting leap seconds
+    pub fn timestamp<T: TimeZone>(dt: &DateTime<T>) -> i64 {
+        count_leap_seconds(&dt.with_timezone(&Utc)).0
+    }
+}
+
+impl Display for RightTz {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "right/{}", self.0.name())
+    }
+}
+
+impl FromStr for RightTz {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Without the prefix, the empty name fails to parse like any other unknown one.
+        let name = s.strip_prefix("right/").unwrap_or_default();
+        name.parse().map(RightTz)
+    }
+}
+
+/// Returns the number of leap seconds added to UTC, less those removed, by a Unix timestamp
+fn leap_count(timestamp: i64) -> i32 {
+    match LEAP_SECONDS.partition_point(|leap| leap.timestamp <= timestamp) {
+        0 => 0,
+        index => LEAP_SECONDS[index - 1].tai_offset - TAI_OFFSET_1972,
+    }
+}
+
+/// Returns the seconds and nanoseconds since the Unix epoch at a time, counting leap seconds
+fn count_leap_seconds(utc: &DateTime<Utc>) -> (i64, u32) {
+    let (secs, nsecs) = (utc.timestamp(), utc.timestamp_subsec_nanos());
+    let secs = secs + i64::from(leap_count(secs));
+    // chrono represents a leap second as the second before it with more than a second's worth
+    // of nanoseconds.
+    match nsecs.checked_sub(1_000_000_000) {
+        Some(nsecs) => (secs + 1, nsecs),
+        None => (secs, nsecs),
+    }
+}
+
+/// Returns the time at a number of seconds and nanoseconds since the Unix epoch, counting leap
+/// seconds
+fn uncount_leap_seconds(secs: i64, nsecs: u32) -> Option<DateTime<Utc>> {
+    let count = |leap: &LeapSecond| i64::from(leap.tai_offset - TAI_OFFSET_1972);
+    let index = LEAP_SECONDS.partition_point(|leap| leap.timestamp + count(leap) <= secs);
+    let leaps = match index {
+        0 => 0,
+        index => count(&LEAP_SECONDS[index - 1]),
+    };
+    // An added leap second goes between the two timestamps around it, which chrono represents
+    // as the first of them with more than a second's worth of nanoseconds.
+    if let Some(next) = LEAP_SECONDS.get(index) {
+        if count(next) > leaps && secs == next.timestamp + leaps {
+            return DateTime::from_timestamp(next.timestamp - 1, nsecs.checked_add(1_000_000_000)?);
+        }
+    }
+    DateTime::from_timestamp(secs.checked_sub(leaps)?, nsecs)
+}
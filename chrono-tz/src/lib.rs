@@ -18,10 +18,10 @@
 //! # extern crate chrono;
 //! # extern crate chrono_tz;
 //! use chrono::{TimeZone, Utc};
-//! use chrono_tz::US::Pacific;
+//! use chrono_tz::America::Los_Angeles;
 //!
 //! # fn main() {
-//! let pacific_time = Pacific.ymd(1990, 5, 6).and_hms(12, 30, 45);
+//! let pacific_time = Los_Angeles.ymd(1990, 5, 6).and_hms(12, 30, 45);
 //! let utc_time = pacific_time.with_timezone(&Utc);
 //! assert_eq!(utc_time, Utc.ymd(1990, 5, 6).and_hms(19, 30, 45));
 //! # }
@@ -59,6 +59,28 @@
 //! # }
 //! ```
 //!
+//! You can get the raw offsets as well if you want to see the standard
+//! UTC offset as well as any special offsets in effect (such as DST)
+//! at a given time. Note that you need to import the `OffsetComponents`
+//! trait.
+//!
+//! ```
+//! # extern crate chrono;
+//! # extern crate chrono_tz;
+//! use chrono::{Duration, TimeZone};
+//! use chrono_tz::Europe::London;
+//! use chrono_tz::OffsetComponents;
+//!
+//! # fn main() {
+//! let london_time = London.ymd(2016, 5, 10).and_hms(12, 0, 0);
+//!
+//! // London typically has zero offset from UTC, but has a 1h adjustment forward
+//! // when summer time is in effect.
+//! assert_eq!(london_time.offset().base_utc_offset(), Duration::hours(0));
+//! assert_eq!(london_time.offset().dst_offset(), Duration::hours(1));
+//! # }
+//! ```
+//!
 //! Adding 24 hours across a daylight savings change causes a change
 //! in local time
 //!
@@ -134,11 +156,12 @@
 #[cfg(feature = "serde")]
 mod serde;
 
+#[cfg(feature = "std")]
 mod binary_search;
-#[cfg(not(any(feature = "case-insensitive", feature = "filter-by-regex")))]
+#[cfg(not(chrono_tz_codegen))]
 use prebuilt::directory;
 mod prebuilt;
-#[cfg(any(feature = "case-insensitive", feature = "filter-by-regex"))]
+#[cfg(chrono_tz_codegen)]
 mod directory {
     #![allow(
         dead_code,
@@ -148,26 +171,58 @@ mod directory {
     )]
     include!(concat!(env!("OUT_DIR"), "/directory.rs"));
 }
+mod coordinates;
+mod country;
+mod leap;
+mod posix;
+#[cfg(feature = "std")]
+mod system;
 mod timezone_impl;
-#[cfg(not(any(feature = "case-insensitive", feature = "filter-by-regex")))]
+mod transitions;
+#[cfg(feature = "std")]
+mod tzfile;
+mod windows;
+#[cfg(not(chrono_tz_codegen))]
 use prebuilt::timezones;
-#[cfg(any(feature = "case-insensitive", feature = "filter-by-regex"))]
+#[cfg(chrono_tz_codegen)]
 mod timezones {
     #![allow(non_camel_case_types, clippy::unreadable_literal)]
     include!(concat!(env!("OUT_DIR"), "/timezones.rs"));
 }
 
-pub use crate::timezone_impl::{GapInfo, OffsetName, TzOffset};
+#[cfg(feature = "std")]
+pub use crate::coordinates::nearest_zone;
+pub use crate::coordinates::Coordinates;
+pub use crate::country::{country_name, zones_for_country};
+pub use crate::leap::{
+    leap_seconds, leap_seconds_expiry, tai_offset, tai_to_utc, utc_to_tai, LeapSecond, RightTz,
+};
+pub use crate::posix::{ParsePosixTzError, PosixOffset, PosixTimeZone};
+#[cfg(feature = "std")]
+pub use crate::system::{local_tz, SystemTzError};
+pub use crate::timezone_impl::{GapInfo, OffsetComponents, OffsetName, TzOffset};
+pub use crate::transitions::{Transition, Transitions};
+#[cfg(feature = "std")]
+pub use crate::tzfile::{TzFile, TzFileError, TzFileGapInfo, TzFileOffset};
 pub use directory::*;
 pub use timezones::ParseError;
 pub use timezones::Tz;
+pub use timezones::CANONICAL_VARIANTS;
+pub use timezones::COUNTRIES;
 pub use timezones::TZ_VARIANTS;
+pub use IANA_TZDB_DATA_FORM;
 pub use IANA_TZDB_VERSION;
 
 #[cfg(test)]
 mod tests {
+    #[cfg(not(any(feature = "exclude-backward-links", chrono_tz_custom_tzdata)))]
     use super::Africa::Addis_Ababa;
+    // Links from `backward` are parsed as the zone they link to.
+    #[cfg(all(feature = "exclude-backward-links", not(chrono_tz_custom_tzdata)))]
+    use super::Africa::Nairobi as Addis_Ababa;
     use super::America::Danmarkshavn;
+    #[cfg(feature = "exclude-backward-links")]
+    use super::America::New_York as Eastern;
     use super::America::Scoresbysund;
     use super::Antarctica::Casey;
     use super::Asia::Dhaka;
@@ -178,15 +233,24 @@ mod tests {
     use super::Europe::Vilnius;
     use super::Europe::Warsaw;
     use super::GapInfo;
+    use super::OffsetComponents;
     use super::Pacific::Apia;
     use super::Pacific::Noumea;
     use super::Pacific::Tahiti;
+    use super::Transition;
     use super::Tz;
+    use super::CANONICAL_VARIANTS;
+    use super::IANA_TZDB_DATA_FORM;
     use super::IANA_TZDB_VERSION;
+    use super::TZ_VARIANTS;
+    #[cfg(not(feature = "exclude-backward-links"))]
     use super::US::Eastern;
     use super::UTC;
+    use super::{country_name, zones_for_country, COUNTRIES};
+    use super::{tai_offset, tai_to_utc, utc_to_tai, RightTz};
     use chrono::NaiveDateTime;
-    use chrono::{Duration, NaiveDate, TimeZone};
+    use chrono::{DateTime, Datelike, Duration, NaiveDate, Offset, TimeZone, Timelike, Utc};
+    use core::ops::Bound;
 
     #[test]
     fn london_to_berlin() {
@@ -254,6 +318,234 @@ mod tests {
         assert_eq!(dt, London.with_ymd_and_hms(1847, 12, 1, 0, 1, 15).unwrap());
     }
 
+    #[test]
+    fn offset_components() {
+        let dt = Eastern.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+        assert_eq!(dt.offset().base_utc_offset(), Duration::hours(-5));
+        assert_eq!(dt.offset().dst_offset(), Duration::hours(0));
+        assert!(!dt.offset().is_dst());
+
+        let dt = Eastern.with_ymd_and_hms(2024, 7, 15, 12, 0, 0).unwrap();
+        assert_eq!(dt.offset().base_utc_offset(), Duration::hours(-5));
+        assert_eq!(dt.offset().dst_offset(), Duration::hours(1));
+        assert!(dt.offset().is_dst());
+    }
+
+    #[test]
+    fn offset_components_double_summer_time() {
+        // During the war London was two hours ahead of its base offset.
+        let dt = London.with_ymd_and_hms(1944, 6, 1, 12, 0, 0).unwrap();
+        assert_eq!(dt.offset().base_utc_offset(), Duration::hours(0));
+        assert_eq!(dt.offset().dst_offset(), Duration::hours(2));
+        assert_eq!(dt.offset().to_string(), "BDST");
+    }
+
+    #[test]
+    fn dst_past_2100() {
+        let dt = Eastern.with_ymd_and_hms(2150, 1, 15, 12, 0, 0).unwrap();
+        assert_eq!(dt.offset().to_string(), "EST");
+        let dt = Eastern.with_ymd_and_hms(2150, 7, 15, 12, 0, 0).unwrap();
+        assert_eq!(dt.offset().to_string(), "EDT");
+
+        let dt = Adelaide.with_ymd_and_hms(2150, 1, 15, 12, 0, 0).unwrap();
+        assert_eq!(dt.offset().to_string(), "ACDT");
+        let dt = Adelaide.with_ymd_and_hms(2150, 7, 15, 12, 0, 0).unwrap();
+        assert_eq!(dt.offset().to_string(), "ACST");
+    }
+
+    #[test]
+    fn negative_dst() {
+        // Irish Standard Time is the standard time of Dublin, except in the rearguard form.
+        let rearguard = IANA_TZDB_DATA_FORM == "rearguard";
+        let winter = Tz::Europe__Dublin
+            .with_ymd_and_hms(2024, 1, 15, 12, 0, 0)
+            .unwrap();
+        let summer = Tz::Europe__Dublin
+            .with_ymd_and_hms(2024, 7, 15, 12, 0, 0)
+            .unwrap();
+        assert_eq!(winter.offset().fix().local_minus_utc(), 0);
+        assert_eq!(summer.offset().fix().local_minus_utc(), 3600);
+        assert_eq!(winter.offset().to_string(), "GMT");
+        assert_eq!(summer.offset().to_string(), "IST");
+        if rearguard {
+            assert_eq!(winter.offset().base_utc_offset(), Duration::hours(0));
+            assert_eq!(winter.offset().dst_offset(), Duration::hours(0));
+            assert!(!winter.offset().is_dst());
+            assert_eq!(summer.offset().dst_offset(), Duration::hours(1));
+            assert!(summer.offset().is_dst());
+        } else {
+            assert_eq!(winter.offset().base_utc_offset(), Duration::hours(1));
+            assert_eq!(winter.offset().dst_offset(), Duration::hours(-1));
+            assert!(winter.offset().is_dst());
+            assert_eq!(summer.offset().dst_offset(), Duration::hours(0));
+            assert!(!summer.offset().is_dst());
+        }
+    }
+
+    #[test]
+    fn negative_dst_past_2100() {
+        let rearguard = IANA_TZDB_DATA_FORM == "rearguard";
+        let dt = Tz::Europe__Dublin
+            .with_ymd_and_hms(2150, 1, 15, 12, 0, 0)
+            .unwrap();
+        assert_eq!(dt.offset().to_string(), "GMT");
+        let dst = if rearguard { 0 } else { -1 };
+        assert_eq!(dt.offset().dst_offset(), Duration::hours(dst));
+        let dt = Tz::Europe__Dublin
+            .with_ymd_and_hms(2150, 7, 15, 12, 0, 0)
+            .unwrap();
+        assert_eq!(dt.offset().to_string(), "IST");
+        let dst = if rearguard { 1 } else { 0 };
+        assert_eq!(dt.offset().dst_offset(), Duration::hours(dst));
+    }
+
+    #[test]
+    fn transitions_in_range() {
+        let start = Utc.with_ymd_and_hms(2016, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2017, 1, 1, 0, 0, 0).unwrap();
+        let transitions = London.transitions_in(start..end);
+        assert_eq!(transitions.len(), 2);
+
+        let [spring, autumn]: [Transition; 2] = transitions.collect::<Vec<_>>().try_into().unwrap();
+        assert_eq!(
+            spring.at,
+            Utc.with_ymd_and_hms(2016, 3, 27, 1, 0, 0).unwrap()
+        );
+        assert_eq!(spring.before.to_string(), "GMT");
+        assert_eq!(spring.after.to_string(), "BST");
+        assert_eq!(
+            autumn.at,
+            Utc.with_ymd_and_hms(2016, 10, 30, 1, 0, 0).unwrap()
+        );
+        assert_eq!(autumn.before.to_string(), "BST");
+        assert_eq!(autumn.after.to_string(), "GMT");
+
+        // Both ends of the range are respected exactly.
+        assert_eq!(London.transitions_in(spring.at..autumn.at).count(), 1);
+        assert_eq!(London.transitions_in(spring.at..=autumn.at).count(), 2);
+        assert_eq!(
+            London
+                .transitions_in((Bound::Excluded(spring.at), Bound::Unbounded))
+                .next(),
+            Some(autumn)
+        );
+    }
+
+    #[test]
+    fn transitions_double_ended() {
+        let transitions = UTC.transitions();
+        assert_eq!(transitions.len(), 0);
+
+        let first = Eastern.transitions().next().unwrap();
+        assert_eq!(first.before.to_string(), "LMT");
+        assert_eq!(first.after.to_string(), "EST");
+
+        let end = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let last = Eastern.transitions_in(..end).next_back().unwrap();
+        assert_eq!(last.at, Utc.with_ymd_and_hms(2023, 11, 5, 6, 0, 0).unwrap());
+
+        let mut transitions = Eastern.transitions_in(..end);
+        let len = transitions.len();
+        assert_eq!(transitions.nth(len - 1), Some(last));
+        assert_eq!(transitions.next(), None);
+        assert_eq!(transitions.next_back(), None);
+    }
+
+    #[test]
+    fn transitions_len_near_the_end() {
+        // The iterator only counts the transitions chrono can represent, which the last ones
+        // the rules produce may not be.
+        let start = NaiveDateTime::MAX.and_utc() - Duration::days(800);
+        for &tz in TZ_VARIANTS.iter() {
+            let transitions = tz.transitions_in(start..);
+            let len = transitions.len();
+            assert_eq!(transitions.clone().count(), len);
+            assert_eq!(transitions.rev().count(), len);
+        }
+    }
+
+    #[test]
+    fn next_and_previous_transition() {
+        let spring = Utc.with_ymd_and_hms(2024, 3, 10, 7, 0, 0).unwrap();
+        let autumn = Utc.with_ymd_and_hms(2024, 11, 3, 6, 0, 0).unwrap();
+        let summer = Utc.with_ymd_and_hms(2024, 7, 1, 0, 0, 0).unwrap();
+
+        let next = Eastern.next_transition(&summer).unwrap();
+        assert_eq!(next.at, autumn);
+        assert_eq!(next.before.to_string(), "EDT");
+        assert_eq!(next.after.to_string(), "EST");
+        let previous = Eastern.previous_transition(&summer).unwrap();
+        assert_eq!(previous.at, spring);
+        assert_eq!(previous.after.to_string(), "EDT");
+
+        // A transition is neither after nor before its own instant.
+        assert_eq!(Eastern.next_transition(&spring).unwrap().at, autumn);
+        assert_eq!(Eastern.previous_transition(&autumn).unwrap().at, spring);
+        let just_after = spring + Duration::nanoseconds(1);
+        assert_eq!(Eastern.previous_transition(&just_after).unwrap().at, spring);
+    }
+
+    #[test]
+    fn next_transition_past_2100() {
+        let instant = Utc.with_ymd_and_hms(2099, 12, 1, 0, 0, 0).unwrap();
+        let next = London.next_transition(&instant).unwrap();
+        assert_eq!(next.at, Utc.with_ymd_and_hms(2100, 3, 28, 1, 0, 0).unwrap());
+        assert_eq!(
+            London.previous_transition(&next.at),
+            London.previous_transition(&instant)
+        );
+
+        let instant = Utc.with_ymd_and_hms(2150, 6, 1, 0, 0, 0).unwrap();
+        let next = London.next_transition(&instant).unwrap();
+        assert_eq!(
+            next.at,
+            Utc.with_ymd_and_hms(2150, 10, 25, 1, 0, 0).unwrap()
+        );
+        let previous = London.previous_transition(&instant).unwrap();
+        assert_eq!(
+            previous.at,
+            Utc.with_ymd_and_hms(2150, 3, 29, 1, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn no_transition() {
+        let instant = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(UTC.next_transition(&instant), None);
+        assert_eq!(UTC.previous_transition(&instant), None);
+
+        let first = Eastern.transitions().next().unwrap();
+        assert_eq!(Eastern.previous_transition(&first.at), None);
+        // Samoa hasn't observed DST since 2021.
+        assert_eq!(Apia.next_transition(&instant), None);
+    }
+
+    #[test]
+    fn transitions_match_offsets() {
+        let start = Utc.with_ymd_and_hms(1900, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2200, 1, 1, 0, 0, 0).unwrap();
+        for tz in [
+            London,
+            Eastern,
+            Adelaide,
+            Tz::Europe__Dublin,
+            Tz::America__Santiago,
+        ] {
+            let mut previous: Option<Transition> = None;
+            for transition in tz.transitions_in(start..end) {
+                let before = (transition.at - Duration::seconds(1)).with_timezone(&tz);
+                assert_eq!(*before.offset(), transition.before);
+                assert_eq!(*transition.at.with_timezone(&tz).offset(), transition.after);
+                if let Some(previous) = previous {
+                    assert!(previous.at < transition.at);
+                    assert_eq!(previous.after, transition.before);
+                }
+                previous = Some(transition);
+            }
+            assert!(previous.unwrap().at > Utc.with_ymd_and_hms(2190, 1, 1, 0, 0, 0).unwrap());
+        }
+    }
+
     #[test]
     fn london_dst() {
         let dt = London.with_ymd_and_hms(2016, 3, 10, 5, 0, 0).unwrap();
@@ -314,6 +606,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn inverse_london_past_2100() {
+        test_inverse(London, 2098, 2102);
+    }
+
+    #[test]
+    fn inverse_adelaide_past_2100() {
+        test_inverse(Adelaide, 2098, 2102);
+    }
+
     #[test]
     fn inverse_london() {
         test_inverse(London, 1989, 1994);
@@ -409,6 +711,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ambiguous_time_past_2100() {
+        let ambiguous = London.with_ymd_and_hms(2150, 10, 25, 1, 0, 0);
+        let earliest_utc = NaiveDate::from_ymd_opt(2150, 10, 25)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        assert_eq!(
+            ambiguous.earliest().unwrap(),
+            London.from_utc_datetime(&earliest_utc)
+        );
+        let latest_utc = NaiveDate::from_ymd_opt(2150, 10, 25)
+            .unwrap()
+            .and_hms_opt(1, 0, 0)
+            .unwrap();
+        assert_eq!(
+            ambiguous.latest().unwrap(),
+            London.from_utc_datetime(&latest_utc)
+        );
+    }
+
     #[test]
     fn ambiguous_time_2() {
         let ambiguous = London.with_ymd_and_hms(2016, 10, 30, 1, 30, 0);
@@ -493,6 +816,7 @@ mod tests {
         assert_eq!(London.name(), "Europe/London");
         assert_eq!(Tz::Africa__Abidjan.name(), "Africa/Abidjan");
         assert_eq!(Tz::UTC.name(), "UTC");
+        #[cfg(not(feature = "exclude-backward-links"))]
         assert_eq!(Tz::Zulu.name(), "Zulu");
     }
 
@@ -501,6 +825,7 @@ mod tests {
         assert_eq!(format!("{London}"), "Europe/London");
         assert_eq!(format!("{}", Tz::Africa__Abidjan), "Africa/Abidjan");
         assert_eq!(format!("{}", Tz::UTC), "UTC");
+        #[cfg(not(feature = "exclude-backward-links"))]
         assert_eq!(format!("{}", Tz::Zulu), "Zulu");
     }
 
@@ -528,9 +853,13 @@ mod tests {
         let dt = Casey.with_ymd_and_hms(2022, 11, 1, 0, 0, 0).unwrap();
         assert_eq!(format!("{}", dt.offset()), "+11");
         assert_eq!(format!("{:?}", dt.offset()), "+11");
-        let dt = Addis_Ababa.with_ymd_and_hms(1937, 2, 1, 0, 0, 0).unwrap();
-        assert_eq!(format!("{}", dt.offset()), "+0245");
-        assert_eq!(format!("{:?}", dt.offset()), "+0245");
+        // Other builds of the data may come with the zones of `backzone`.
+        #[cfg(not(chrono_tz_custom_tzdata))]
+        {
+            let dt = Addis_Ababa.with_ymd_and_hms(1937, 2, 1, 0, 0, 0).unwrap();
+            assert_eq!(format!("{}", dt.offset()), "+0245");
+            assert_eq!(format!("{:?}", dt.offset()), "+0245");
+        }
     }
 
     fn gap_info_test(tz: Tz, gap_begin: NaiveDateTime, gap_end: NaiveDateTime) {
@@ -564,6 +893,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn gap_info_europe_london_past_2100() {
+        gap_info_test(
+            Tz::Europe__London,
+            NaiveDate::from_ymd_opt(2150, 3, 29)
+                .unwrap()
+                .and_hms_opt(1, 0, 0)
+                .unwrap(),
+            NaiveDate::from_ymd_opt(2150, 3, 29)
+                .unwrap()
+                .and_hms_opt(2, 0, 0)
+                .unwrap(),
+        );
+    }
+
     #[test]
     fn gap_info_europe_dublin() {
         gap_info_test(
@@ -610,6 +954,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "exclude-backward-links"))]
     fn gap_info_libya_2013() {
         gap_info_test(
             Tz::Libya,
@@ -650,4 +995,274 @@ mod tests {
             "+08"
         );
     }
+
+    #[test]
+    #[cfg(not(feature = "exclude-backward-links"))]
+    fn windows_id() {
+        assert_eq!(
+            Tz::from_windows_id("Eastern Standard Time", None),
+            Some(Tz::America__New_York)
+        );
+        assert_eq!(
+            Tz::from_windows_id("Eastern Standard Time", Some("BS")),
+            Some(Tz::America__Nassau)
+        );
+        assert_eq!(
+            Tz::from_windows_id("Eastern Standard Time", Some("ca")),
+            Some(Tz::America__Toronto)
+        );
+        // A territory the ID doesn't cover falls back to the main zone.
+        assert_eq!(
+            Tz::from_windows_id("Eastern Standard Time", Some("FR")),
+            Some(Tz::America__New_York)
+        );
+        assert_eq!(
+            Tz::from_windows_id("Pacific Standard Time", Some("ZZ")),
+            Some(Tz::PST8PDT)
+        );
+        assert_eq!(Tz::from_windows_id("UTC", None), Some(Tz::Etc__UTC));
+        assert_eq!(Tz::from_windows_id("Nowhere Standard Time", None), None);
+
+        assert_eq!(
+            Tz::America__Indiana__Indianapolis.windows_id(),
+            Some("US Eastern Standard Time")
+        );
+        assert_eq!(Tz::Asia__Kolkata.windows_id(), Some("India Standard Time"));
+        assert_eq!(Tz::Asia__Calcutta.windows_id(), Some("India Standard Time"));
+        assert_eq!(Tz::Antarctica__Troll.windows_id(), None);
+    }
+
+    #[test]
+    fn windows_id_roundtrip() {
+        let mut found = 0;
+        for tz in TZ_VARIANTS {
+            let Some(id) = tz.windows_id() else {
+                continue;
+            };
+            found += 1;
+            let main = Tz::from_windows_id(id, None).unwrap();
+            assert_eq!(main.windows_id(), Some(id), "{tz} -> {id} -> {main}");
+        }
+        // Most of the names from `backward` have a Windows ID too.
+        #[cfg(not(feature = "exclude-backward-links"))]
+        assert!(found > 400);
+        #[cfg(feature = "exclude-backward-links")]
+        assert!(found > 300);
+    }
+
+    #[test]
+    fn country_zones() {
+        #[cfg(not(any(feature = "exclude-backward-links", chrono_tz_custom_tzdata)))]
+        {
+            assert_eq!(zones_for_country("no"), [Tz::Europe__Oslo]);
+            assert_eq!(
+                zones_for_country("DE"),
+                [Tz::Europe__Berlin, Tz::Europe__Busingen]
+            );
+        }
+        // zone1970.tab describes Crimea as part of Russia, while zone.tab lists it under Ukraine.
+        assert!(zones_for_country("RU").contains(&Tz::Europe__Simferopol));
+        assert!(zones_for_country("UA").contains(&Tz::Europe__Simferopol));
+        // Uninhabited, so without a zone of its own.
+        assert!(zones_for_country("BV").is_empty());
+        assert!(zones_for_country("").is_empty());
+        assert!(zones_for_country("BRA").is_empty());
+
+        assert_eq!(country_name("de"), Some("Germany"));
+        assert_eq!(country_name("Deutschland"), None);
+        assert_eq!(Tz::Europe__Simferopol.countries(), ["RU", "UA"]);
+    }
+
+    #[test]
+    fn country_zones_roundtrip() {
+        for (code, _) in COUNTRIES {
+            for tz in zones_for_country(code) {
+                assert!(tz.countries().contains(&code), "{code} {tz}");
+            }
+        }
+        for tz in TZ_VARIANTS {
+            for code in tz.countries() {
+                assert!(zones_for_country(code).contains(&tz), "{code} {tz}");
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "exclude-backward-links"))]
+    fn coordinates() {
+        let berlin = Berlin.coordinates().unwrap();
+        assert!((berlin.latitude - 52.5).abs() < 1e-9);
+        assert!((berlin.longitude - (13.0 + 22.0 / 60.0)).abs() < 1e-9);
+        // Links without an entry of their own use their target's.
+        assert_eq!(Eastern.coordinates(), Tz::America__New_York.coordinates());
+        assert_ne!(Tz::Europe__Oslo.coordinates(), Berlin.coordinates());
+        assert_eq!(Tz::Etc__GMTPlus5.coordinates(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn nearest_zone() {
+        assert_eq!(super::nearest_zone(51.5, -0.1), Some(Tz::Europe__London));
+        assert_eq!(
+            super::nearest_zone(-33.9, 151.2),
+            Some(Tz::Australia__Sydney)
+        );
+        // Across the antimeridian from Fiji
+        assert_eq!(super::nearest_zone(-18.0, -179.9), Some(Tz::Pacific__Fiji));
+        assert_eq!(
+            super::nearest_zone(90.0, 0.0).map(|tz| tz.coordinates().is_some()),
+            Some(true)
+        );
+        assert_eq!(super::nearest_zone(90.1, 0.0), None);
+        assert_eq!(super::nearest_zone(f64::NAN, 0.0), None);
+        assert_eq!(super::nearest_zone(0.0, f64::INFINITY), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn nearest_zone_matches_exhaustive_search() {
+        let distance = |a: super::Coordinates, b: super::Coordinates| {
+            let (a_lat, b_lat) = (a.latitude.to_radians(), b.latitude.to_radians());
+            let longitude = (b.longitude - a.longitude).to_radians();
+            (a_lat.sin() * b_lat.sin() + a_lat.cos() * b_lat.cos() * longitude.cos())
+                .clamp(-1.0, 1.0)
+                .acos()
+        };
+
+        for latitude in (-90..=90).step_by(7) {
+            for longitude in (-180..180).step_by(11) {
+                let point = super::Coordinates {
+                    latitude: f64::from(latitude),
+                    longitude: f64::from(longitude),
+                };
+                let nearest = super::nearest_zone(point.latitude, point.longitude).unwrap();
+                let best = TZ_VARIANTS
+                    .iter()
+                    .filter_map(|tz| tz.coordinates())
+                    .map(|zone| distance(point, zone))
+                    .fold(f64::INFINITY, f64::min);
+                let found = distance(point, nearest.coordinates().unwrap());
+                assert!(found - best < 1e-9, "{latitude} {longitude}: {nearest}");
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "exclude-backward-links"))]
+    fn canonical() {
+        assert_eq!(Eastern.canonical(), Tz::America__New_York);
+        assert_eq!(Tz::Asia__Calcutta.canonical(), Tz::Asia__Kolkata);
+        assert_eq!(Tz::UTC.canonical(), Tz::Etc__UTC);
+        assert!(Tz::UTC.is_link());
+        assert!(!Tz::Etc__UTC.is_link());
+        assert!(Tz::Europe__Andorra.aliases().is_empty());
+
+        let mut aliases = Tz::Asia__Kolkata.aliases().to_vec();
+        aliases.sort_by_key(|tz| tz.name());
+        assert_eq!(aliases, [Tz::Asia__Calcutta]);
+    }
+
+    #[test]
+    fn canonical_variants() {
+        for tz in TZ_VARIANTS {
+            let canonical = tz.canonical();
+            assert!(!canonical.is_link(), "{tz}");
+            assert_eq!(CANONICAL_VARIANTS.contains(&tz), !tz.is_link(), "{tz}");
+            for alias in tz.aliases() {
+                assert_eq!(alias.canonical(), canonical, "{tz} {alias}");
+                assert!(alias.aliases().contains(&tz), "{tz} {alias}");
+            }
+            assert!(!tz.aliases().contains(&tz), "{tz}");
+        }
+    }
+
+    #[test]
+    #[cfg(not(any(feature = "exclude-backward-links", chrono_tz_custom_tzdata)))]
+    fn deprecated_alias() {
+        assert!(Eastern.is_deprecated_alias());
+        assert!(Tz::Asia__Calcutta.is_deprecated_alias());
+        assert!(Tz::UTC.is_deprecated_alias());
+        assert!(!Tz::America__New_York.is_deprecated_alias());
+        // A link, but from `etcetera` rather than `backward`
+        assert!(!Tz::GMT.is_deprecated_alias());
+        assert!(TZ_VARIANTS
+            .iter()
+            .filter(|tz| tz.is_deprecated_alias())
+            .all(|tz| tz.is_link()));
+    }
+
+    #[test]
+    fn leap_seconds() {
+        let leap_seconds = super::leap_seconds();
+        assert_eq!(leap_seconds.len(), 27);
+        assert!(leap_seconds
+            .windows(2)
+            .all(|w| w[0].timestamp < w[1].timestamp));
+        let last = leap_seconds[26];
+        assert_eq!(
+            DateTime::from_timestamp(last.timestamp, 0),
+            Some(Utc.with_ymd_and_hms(2017, 1, 1, 0, 0, 0).unwrap())
+        );
+        assert_eq!(last.tai_offset, 37);
+        assert!(
+            super::leap_seconds_expiry().unwrap()
+                > Utc.with_ymd_and_hms(2017, 1, 1, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn tai_roundtrip() {
+        let leap_second = NaiveDate::from_ymd_opt(1972, 6, 30)
+            .unwrap()
+            .and_hms_nano_opt(23, 59, 59, 1_250_000_000)
+            .unwrap()
+            .and_utc();
+        let before = Utc.with_ymd_and_hms(1972, 6, 30, 23, 59, 59).unwrap();
+        let after = Utc.with_ymd_and_hms(1972, 7, 1, 0, 0, 0).unwrap();
+        let tai = |utc: DateTime<Utc>| utc_to_tai(&utc).unwrap();
+        assert_eq!(tai(after) - tai(before), Duration::seconds(2));
+        assert_eq!(tai(leap_second) - tai(before), Duration::milliseconds(1250));
+        for utc in [before, leap_second, after] {
+            assert_eq!(tai_to_utc(&tai(utc)), Some(utc));
+        }
+
+        let start = Utc.with_ymd_and_hms(1972, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(tai_offset(&start), Some(10));
+        assert_eq!(tai_offset(&(start - Duration::seconds(1))), None);
+        assert_eq!(tai_to_utc(&(tai(start) - Duration::seconds(1))), None);
+        let mut utc = start;
+        while utc.year() < 2030 {
+            assert_eq!(tai_to_utc(&tai(utc)), Some(utc));
+            utc += Duration::hours(13);
+        }
+    }
+
+    #[test]
+    fn right_timestamps() {
+        let right = RightTz(Tz::Asia__Tokyo);
+        assert_eq!(right.to_string(), "right/Asia/Tokyo");
+        assert_eq!(right.to_string().parse(), Ok(right));
+        assert!("Asia/Tokyo".parse::<RightTz>().is_err());
+        assert!("right/".parse::<RightTz>().is_err());
+
+        // Before the first leap second, right timestamps are the same as Unix ones.
+        let epoch = Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(RightTz::timestamp(&epoch), 0);
+        assert_eq!(
+            right.timestamp_opt(0, 0),
+            Some(epoch.with_timezone(&Tz::Asia__Tokyo))
+        );
+
+        // 2016-12-31 23:59:59 UTC, then the leap second, then 2017-01-01 00:00:00 UTC
+        let before = 1_483_228_799 + 26;
+        let times = (before..before + 3)
+            .map(|secs| right.timestamp_opt(secs, 0).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(times[0].naive_utc().second(), 59);
+        assert_eq!(times[1].naive_utc().nanosecond(), 1_000_000_000);
+        assert_eq!(times[2].naive_utc().second(), 0);
+        for (secs, dt) in (before..).zip(&times) {
+            assert_eq!(RightTz::timestamp(dt), secs);
+        }
+    }
 }
@@ -0,0 +1,929 @@
+use core::fmt::{self, Debug, Display, Formatter};
+use core::str::{self, FromStr};
+
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, NaiveTime,
+    Offset, TimeZone,
+};
+
+use crate::timezone_impl::{offset_from_local_date, FixedTimespan, OffsetComponents, OffsetName};
+
+/// The rules that keep recurring after the last transition of a timezone
+///
+/// This mirrors the shape of a POSIX `TZ` string such as `EST5EDT,M3.2.0,M11.1.0`. For `Tz` it is
+/// generated from the rules that are still in effect at the end of the tz database, otherwise it
+/// is parsed from such a string.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PosixTz<T = FixedTimespan> {
+    /// The timespan in effect whenever daylight saving time isn't
+    pub std: T,
+    /// The daylight saving timespan and the rules that switch to and from it
+    pub dst: Option<PosixDst<T>>,
+}
+
+/// The daylight saving part of a [`PosixTz`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PosixDst<T = FixedTimespan> {
+    /// The timespan in effect between `start` and `end`
+    pub timespan: T,
+    /// When the switch from standard time to `timespan` happens
+    pub start: PosixRule,
+    /// When the switch back to standard time happens
+    pub end: PosixRule,
+}
+
+/// A yearly transition instant
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PosixRule {
+    /// The day on which the transition happens
+    pub day: PosixDay,
+    /// The local time of the transition in seconds, measured in the offset in effect before it
+    pub time: i32,
+}
+
+/// A day of the year in one of the three forms allowed by POSIX
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PosixDay {
+    /// `Jn`: the day of the year from 1 to 365, never counting February 29
+    Julian(u16),
+    /// `n`: the zero-based day of the year from 0 to 365, counting February 29 in leap years
+    Zero(u16),
+    /// `Mm.w.d`: weekday `d` (0 is Sunday) of week `w` (5 is the last) of month `m`
+    MonthWeekday(u8, u8, u8),
+}
+
+/// A period with a fixed offset that a [`PosixTz`] switches between
+pub(crate) trait Timespan: Copy + PartialEq {
+    /// The total offset from UTC in seconds
+    fn total_offset(&self) -> i32;
+}
+
+impl Timespan for FixedTimespan {
+    fn total_offset(&self) -> i32 {
+        self.offset()
+    }
+}
+
+impl PosixDay {
+    fn date(self, year: i32) -> Option<NaiveDate> {
+        match self {
+            PosixDay::Julian(day) => {
+                let leap_day = is_leap(year) && day >= 60;
+                NaiveDate::from_yo_opt(year, day as u32 + leap_day as u32)
+            }
+            // Day 365 of a year without February 29 is January 1st of the next, like glibc has it.
+            PosixDay::Zero(day) => {
+                NaiveDate::from_yo_opt(year, 1)?.checked_add_signed(Duration::days(day as i64))
+            }
+            PosixDay::MonthWeekday(month, week, weekday) => {
+                let first = NaiveDate::from_ymd_opt(year, month as u32, 1)?;
+                let first_weekday = first.weekday().num_days_from_sunday();
+                let mut day = 1 + (weekday as u32 + 7 - first_weekday) % 7 + (week as u32 - 1) * 7;
+                while day > month_length(year, month) {
+                    day -= 7;
+                }
+                NaiveDate::from_ymd_opt(year, month as u32, day)
+            }
+        }
+    }
+}
+
+impl PosixRule {
+    /// The Unix timestamp at which this rule takes effect in `year`, given the total UTC offset
+    /// in effect just before it
+    fn timestamp(&self, year: i32, offset_before: i32) -> Option<i64> {
+        let midnight = self.day.date(year)?.and_time(NaiveTime::MIN).and_utc();
+        Some(midnight.timestamp() + self.time as i64 - offset_before as i64)
+    }
+}
+
+impl<T: Timespan> PosixTz<T> {
+    /// The transitions of the given year in chronological order, as the Unix timestamp of the
+    /// transition with the timespans in effect before and after it
+    pub(crate) fn transitions(&self, year: i32) -> Option<[(i64, T, T); 2]> {
+        let dst = self.dst?;
+        let start = dst.start.timestamp(year, self.std.total_offset())?;
+        let end = dst.end.timestamp(year, dst.timespan.total_offset())?;
+        let start = (start, self.std, dst.timespan);
+        let end = (end, dst.timespan, self.std);
+        Some(match start.0 < end.0 {
+            true => [start, end],
+            false => [end, start],
+        })
+    }
+
+    /// The number of transitions after `since`, up to and including `until`
+    pub(crate) fn count_transitions(&self, since: i64, until: i64) -> usize {
+        if until <= since {
+            return 0;
+        }
+
+        match (self.transitions_until(since), self.transitions_until(until)) {
+            (Some(first), Some(last)) => (last - first).max(0) as usize,
+            _ => 0,
+        }
+    }
+
+    /// The transition `n` places after `since`, counting from zero
+    pub(crate) fn nth_transition(&self, since: i64, n: usize) -> Option<(i64, T, T)> {
+        let index = self
+            .transitions_until(since)?
+            .checked_add(i64::try_from(n).ok()?)?;
+        let year = i32::try_from(index.div_euclid(2)).ok()?;
+        self.transitions(year)
+            .map(|transitions| transitions[index.rem_euclid(2) as usize])
+    }
+
+    /// The timespan in effect at the given Unix timestamp
+    pub(crate) fn timespan_at(&self, timestamp: i64) -> T {
+        let year = match self.year_of(timestamp) {
+            Some(year) => year,
+            None => return self.std,
+        };
+
+        let mut transitions = self.transitions_around(year);
+        match transitions
+            .clone()
+            .take_while(|(at, ..)| *at <= timestamp)
+            .last()
+        {
+            Some((_, _, after)) => after,
+            None => transitions.next().map_or(self.std, |(_, before, _)| before),
+        }
+    }
+
+    /// The timespans that could be in effect at the given local timestamp, earliest first
+    pub(crate) fn local_timespans(&self, local: i64) -> LocalResult<T> {
+        let dst = match self.dst {
+            Some(dst) => dst.timespan,
+            None => return LocalResult::Single(self.std),
+        };
+
+        let exists =
+            |timespan: T| self.timespan_at(local - timespan.total_offset() as i64) == timespan;
+        match (exists(self.std), exists(dst)) {
+            (true, true) if self.std.total_offset() > dst.total_offset() => {
+                LocalResult::Ambiguous(self.std, dst)
+            }
+            (true, true) => LocalResult::Ambiguous(dst, self.std),
+            (true, false) => LocalResult::Single(self.std),
+            (false, true) => LocalResult::Single(dst),
+            (false, false) => LocalResult::None,
+        }
+    }
+
+    /// If the given local timestamp is skipped over by a transition, returns the Unix timestamp
+    /// of that transition along with the timespan in effect before it
+    pub(crate) fn gap_at(&self, local: i64) -> Option<(i64, T)> {
+        let year = DateTime::from_timestamp(local, 0)?.year();
+        (year - 1..=year + 1)
+            .filter_map(|year| self.transitions(year))
+            .flatten()
+            .find(|&(at, before, after)| {
+                at + before.total_offset() as i64 <= local
+                    && local < at + after.total_offset() as i64
+            })
+            .map(|(at, before, _)| (at, before))
+    }
+
+    /// The transitions of the years around `year` in chronological order. With the transition
+    /// times RFC 8536 allows, such as `J365/25` or `M1.1.0/-26`, the transitions of a year can
+    /// fall in the year before or after it.
+    fn transitions_around(&self, year: i32) -> impl Iterator<Item = (i64, T, T)> + Clone + '_ {
+        [year.checked_sub(1), Some(year), year.checked_add(1)]
+            .into_iter()
+            .flat_map(move |year| year.and_then(|year| self.transitions(year)))
+            .flatten()
+    }
+
+    /// The index of the first transition after `timestamp`, where the transitions of each year
+    /// `y` are numbered `2 * y` and `2 * y + 1`
+    fn transitions_until(&self, timestamp: i64) -> Option<i64> {
+        let year = self.year_of(timestamp)?;
+        // The transitions of the years before the one before all come before the timestamp,
+        // and the ones of the years after the one after all come after it.
+        let within = self
+            .transitions_around(year)
+            .filter(|(at, ..)| *at <= timestamp)
+            .count();
+        Some(2 * (year as i64 - 1) + within as i64)
+    }
+
+    /// The year the given Unix timestamp falls in, going by standard time
+    fn year_of(&self, timestamp: i64) -> Option<i32> {
+        let local = timestamp.checked_add(self.std.total_offset() as i64)?;
+        DateTime::from_timestamp(local, 0)
+            .or_else(|| DateTime::from_timestamp(timestamp, 0))
+            .map(|local| local.year())
+    }
+
+    /// The largest total offset from UTC this timezone can have
+    pub(crate) fn max_offset(&self) -> i32 {
+        match self.dst {
+            Some(dst) => self.std.total_offset().max(dst.timespan.total_offset()),
+            None => self.std.total_offset(),
+        }
+    }
+}
+
+impl<T> PosixTz<T> {
+    /// Converts the timespans into another type, given whether each one is the DST one
+    pub(crate) fn map<U>(self, mut f: impl FnMut(T, bool) -> U) -> PosixTz<U> {
+        PosixTz {
+            std: f(self.std, false),
+            dst: self.dst.map(|dst| PosixDst {
+                timespan: f(dst.timespan, true),
+                start: dst.start,
+                end: dst.end,
+            }),
+        }
+    }
+}
+
+/// A timespan as written in a POSIX `TZ` string, with a borrowed name
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct PosixTimespan<'a> {
+    pub(crate) name: &'a str,
+    /// The total offset east of UTC in seconds; the string itself counts westward
+    pub(crate) offset: i32,
+}
+
+impl Timespan for PosixTimespan<'_> {
+    fn total_offset(&self) -> i32 {
+        self.offset
+    }
+}
+
+impl<'a> PosixTz<PosixTimespan<'a>> {
+    /// Parses a POSIX `TZ` string, including the extensions RFC 8536 allows in TZif footers
+    ///
+    /// Returns `None` if the string is malformed.
+    pub(crate) fn parse(tz: &'a str) -> Option<Self> {
+        let mut parser = Parser { tz, pos: 0 };
+        let std = parser.timespan(None)?;
+        if parser.is_empty() {
+            return Some(PosixTz { std, dst: None });
+        }
+
+        let dst = parser.timespan(Some(std.offset + 60 * 60))?;
+        let (start, end) = match parser.is_empty() {
+            // The default rules are the US ones, just like glibc does.
+            true => (
+                PosixRule {
+                    day: PosixDay::MonthWeekday(3, 2, 0),
+                    time: 2 * 60 * 60,
+                },
+                PosixRule {
+                    day: PosixDay::MonthWeekday(11, 1, 0),
+                    time: 2 * 60 * 60,
+                },
+            ),
+            false => {
+                parser.expect(b',')?;
+                let start = parser.rule()?;
+                parser.expect(b',')?;
+                (start, parser.rule()?)
+            }
+        };
+
+        match parser.is_empty() {
+            true => Some(PosixTz {
+                std,
+                dst: Some(PosixDst {
+                    timespan: dst,
+                    start,
+                    end,
+                }),
+            }),
+            false => None,
+        }
+    }
+}
+
+struct Parser<'a> {
+    tz: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn is_empty(&self) -> bool {
+        self.pos == self.tz.len()
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.tz.as_bytes().get(self.pos).copied()
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        match self.peek() == Some(byte) {
+            true => {
+                self.pos += 1;
+                Some(())
+            }
+            false => None,
+        }
+    }
+
+    /// Consumes the longest run of bytes matching `pred`
+    fn take_while(&mut self, pred: impl Fn(u8) -> bool) -> &'a str {
+        let start = self.pos;
+        while self.peek().map_or(false, &pred) {
+            self.pos += 1;
+        }
+        &self.tz[start..self.pos]
+    }
+
+    /// A name followed by an offset, which may be left out if a default is given
+    fn timespan(&mut self, default_offset: Option<i32>) -> Option<PosixTimespan<'a>> {
+        let name = match self.peek()? {
+            b'<' => {
+                self.pos += 1;
+                let name = self.take_while(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'-');
+                self.expect(b'>')?;
+                name
+            }
+            _ => self.take_while(|b| b.is_ascii_alphabetic()),
+        };
+        if name.len() < 3 {
+            return None;
+        }
+
+        let offset = match (self.peek(), default_offset) {
+            (None | Some(b','), Some(offset)) => offset,
+            _ => -self.time(24)?,
+        };
+        // A `FixedOffset` is always less than a day.
+        if offset.abs() >= 24 * 60 * 60 {
+            return None;
+        }
+        Some(PosixTimespan { name, offset })
+    }
+
+    /// `[+|-]hh[:mm[:ss]]`, in seconds
+    fn time(&mut self, max_hours: i32) -> Option<i32> {
+        let sign = match self.peek()? {
+            b'-' => -1,
+            b'+' => 1,
+            _ => 0,
+        };
+        if sign != 0 {
+            self.pos += 1;
+        }
+
+        let hours = self.number(0, max_hours)?;
+        let mut seconds = hours * 60 * 60;
+        if self.expect(b':').is_some() {
+            seconds += self.number(0, 59)? * 60;
+            if self.expect(b':').is_some() {
+                seconds += self.number(0, 59)?;
+            }
+        }
+        Some(if sign < 0 { -seconds } else { seconds })
+    }
+
+    fn number(&mut self, min: i32, max: i32) -> Option<i32> {
+        let digits = self.take_while(|b| b.is_ascii_digit());
+        match digits.len() {
+            1..=3 => Some(digits.parse().ok().filter(|n| (min..=max).contains(n))?),
+            _ => None,
+        }
+    }
+
+    /// `date[/time]`, where the date is `Jn`, `n` or `Mm.w.d`
+    fn rule(&mut self) -> Option<PosixRule> {
+        let day = match self.peek()? {
+            b'J' => {
+                self.pos += 1;
+                PosixDay::Julian(self.number(1, 365)? as u16)
+            }
+            b'M' => {
+                self.pos += 1;
+                let month = self.number(1, 12)? as u8;
+                self.expect(b'.')?;
+                let week = self.number(1, 5)? as u8;
+                self.expect(b'.')?;
+                PosixDay::MonthWeekday(month, week, self.number(0, 6)? as u8)
+            }
+            _ => PosixDay::Zero(self.number(0, 365)? as u16),
+        };
+
+        let time = match self.expect(b'/') {
+            Some(()) => self.time(167)?,
+            None => 2 * 60 * 60,
+        };
+        Some(PosixRule { day, time })
+    }
+}
+
+/// The longest abbreviation a [`PosixTimeZone`] can hold
+const MAX_ABBREVIATION_LEN: usize = 15;
+
+/// An abbreviation stored inline, so that [`PosixTimeZone`] can be `Copy` and needs no allocator
+#[derive(Copy, Clone, PartialEq, Eq)]
+struct Abbreviation {
+    len: u8,
+    bytes: [u8; MAX_ABBREVIATION_LEN],
+}
+
+impl Abbreviation {
+    fn new(name: &str) -> Option<Self> {
+        let mut bytes = [0; MAX_ABBREVIATION_LEN];
+        bytes
+            .get_mut(..name.len())?
+            .copy_from_slice(name.as_bytes());
+        Some(Abbreviation {
+            len: name.len() as u8,
+            bytes,
+        })
+    }
+
+    fn as_str(&self) -> &str {
+        // Abbreviations are made of ASCII characters by construction.
+        str::from_utf8(&self.bytes[..self.len as usize]).unwrap_or_default()
+    }
+}
+
+/// One of the two offsets of a [`PosixTimeZone`]
+#[derive(Copy, Clone, PartialEq, Eq)]
+struct NamedOffset {
+    offset: i32,
+    is_dst: bool,
+    name: Abbreviation,
+}
+
+impl Timespan for NamedOffset {
+    fn total_offset(&self) -> i32 {
+        self.offset
+    }
+}
+
+/// A timezone described by a POSIX `TZ` string, such as `EST5EDT,M3.2.0/2,M11.1.0/2`
+///
+/// This is useful when all that is known about a timezone is the value of a `TZ` variable, as
+/// found on embedded devices or in some legacy configurations. Such a string only describes the
+/// rules currently in effect, so unlike [`Tz`](crate::Tz) it knows nothing of historical changes.
+///
+/// The extensions of [RFC 8536], which allow transition times outside of the usual 0 to 24
+/// hours, are supported. If the string names a daylight saving time zone without any rules,
+/// the current US rules are assumed, like glibc does.
+///
+/// ```
+/// # extern crate chrono;
+/// # extern crate chrono_tz;
+/// use chrono::TimeZone;
+/// use chrono_tz::{OffsetName, PosixTimeZone};
+///
+/// let tz: PosixTimeZone = "EST5EDT,M3.2.0/2,M11.1.0/2".parse().unwrap();
+/// let dt = tz.with_ymd_and_hms(2024, 7, 1, 12, 0, 0).unwrap();
+/// assert_eq!(dt.offset().abbreviation(), "EDT");
+/// assert_eq!(dt.to_rfc3339(), "2024-07-01T12:00:00-04:00");
+///
+/// let tz: PosixTimeZone = "<+0330>-3:30".parse().unwrap();
+/// let dt = tz.with_ymd_and_hms(2024, 7, 1, 12, 0, 0).unwrap();
+/// assert_eq!(dt.offset().abbreviation(), "+0330");
+/// assert_eq!(dt.to_rfc3339(), "2024-07-01T12:00:00+03:30");
+/// ```
+///
+/// [RFC 8536]: https://datatracker.ietf.org/doc/html/rfc8536
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct PosixTimeZone {
+    tz: PosixTz<NamedOffset>,
+}
+
+impl PosixTimeZone {
+    fn offset(&self, timespan: NamedOffset) -> PosixOffset {
+        PosixOffset {
+            tz: *self,
+            timespan,
+        }
+    }
+}
+
+impl FromStr for PosixTimeZone {
+    type Err = ParsePosixTzError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tz = PosixTz::parse(s).ok_or(ParsePosixTzError(()))?;
+        let names = [Some(tz.std.name), tz.dst.map(|dst| dst.timespan.name)];
+        if names
+            .iter()
+            .flatten()
+            .any(|name| name.len() > MAX_ABBREVIATION_LEN)
+        {
+            return Err(ParsePosixTzError(()));
+        }
+
+        Ok(PosixTimeZone {
+            tz: tz.map(|timespan, is_dst| NamedOffset {
+                offset: timespan.offset,
+                is_dst,
+                name: Abbreviation::new(timespan.name).unwrap(),
+            }),
+        })
+    }
+}
+
+impl Display for PosixTimeZone {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let std = &self.tz.std;
+        write_name(f, std.name.as_str())?;
+        write_time(f, -std.offset)?;
+
+        if let Some(dst) = &self.tz.dst {
+            write_name(f, dst.timespan.name.as_str())?;
+            // The offset can be left out if it's an hour ahead of standard time.
+            if dst.timespan.offset != std.offset + 60 * 60 {
+                write_time(f, -dst.timespan.offset)?;
+            }
+            write!(f, ",{},{}", dst.start, dst.end)?;
+        }
+        Ok(())
+    }
+}
+
+impl Debug for PosixTimeZone {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Display for PosixRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.day {
+            PosixDay::Julian(day) => write!(f, "J{day}")?,
+            PosixDay::Zero(day) => write!(f, "{day}")?,
+            PosixDay::MonthWeekday(month, week, weekday) => write!(f, "M{month}.{week}.{weekday}")?,
+        }
+        // The time can be left out if it's the default of 02:00.
+        if self.time != 2 * 60 * 60 {
+            f.write_str("/")?;
+            write_time(f, self.time)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes an abbreviation, quoting it in angle brackets unless it consists only of letters
+fn write_name(f: &mut Formatter<'_>, name: &str) -> fmt::Result {
+    match name.bytes().all(|b| b.is_ascii_alphabetic()) {
+        true => f.write_str(name),
+        false => write!(f, "<{name}>"),
+    }
+}
+
+/// Writes a number of seconds as `[-]h[:mm[:ss]]`
+fn write_time(f: &mut Formatter<'_>, seconds: i32) -> fmt::Result {
+    if seconds < 0 {
+        f.write_str("-")?;
+    }
+    let seconds = seconds.abs();
+    write!(f, "{}", seconds / 3600)?;
+    if seconds % 3600 != 0 {
+        write!(f, ":{:02}", seconds / 60 % 60)?;
+        if seconds % 60 != 0 {
+            write!(f, ":{:02}", seconds % 60)?;
+        }
+    }
+    Ok(())
+}
+
+impl TimeZone for PosixTimeZone {
+    type Offset = PosixOffset;
+
+    fn from_offset(offset: &Self::Offset) -> Self {
+        offset.tz
+    }
+
+    fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<Self::Offset> {
+        offset_from_local_date(self, local)
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<Self::Offset> {
+        match self.tz.local_timespans(local.and_utc().timestamp()) {
+            LocalResult::None => LocalResult::None,
+            LocalResult::Single(a) => LocalResult::Single(self.offset(a)),
+            LocalResult::Ambiguous(a, b) => LocalResult::Ambiguous(self.offset(a), self.offset(b)),
+        }
+    }
+
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> Self::Offset {
+        self.offset_from_utc_datetime(&utc.and_time(NaiveTime::MIN))
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> Self::Offset {
+        self.offset(self.tz.timespan_at(utc.and_utc().timestamp()))
+    }
+}
+
+/// The offset of a [`PosixTimeZone`] at some instant
+///
+/// A POSIX `TZ` string has no IANA identifier, so [`OffsetName::tz_id`] is always empty.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct PosixOffset {
+    tz: PosixTimeZone,
+    timespan: NamedOffset,
+}
+
+impl Offset for PosixOffset {
+    fn fix(&self) -> FixedOffset {
+        FixedOffset::east_opt(self.timespan.offset).unwrap()
+    }
+}
+
+impl OffsetName for PosixOffset {
+    fn tz_id(&self) -> &str {
+        ""
+    }
+
+    fn abbreviation(&self) -> &str {
+        self.timespan.name.as_str()
+    }
+}
+
+impl OffsetComponents for PosixOffset {
+    fn base_utc_offset(&self) -> Duration {
+        Duration::seconds(self.tz.tz.std.offset as i64)
+    }
+
+    fn dst_offset(&self) -> Duration {
+        Duration::seconds((self.timespan.offset - self.tz.tz.std.offset) as i64)
+    }
+
+    fn is_dst(&self) -> bool {
+        self.timespan.is_dst
+    }
+}
+
+impl Display for PosixOffset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.abbreviation())
+    }
+}
+
+impl Debug for PosixOffset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+/// An error returned when parsing an invalid POSIX `TZ` string into a [`PosixTimeZone`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ParsePosixTzError(());
+
+impl Display for ParsePosixTzError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("failed to parse POSIX TZ string")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParsePosixTzError {}
+
+fn is_leap(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn month_length(year: i32, month: u8) -> u32 {
+    match month {
+        2 if is_leap(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PosixDay, PosixDst, PosixRule, PosixTimeZone, PosixTz};
+    use crate::timezone_impl::FixedTimespan;
+    use crate::{OffsetComponents, OffsetName};
+    use chrono::{Duration, LocalResult, NaiveDate, Offset, TimeZone, Utc};
+
+    fn date(year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(year, month, day)
+    }
+
+    #[test]
+    fn month_weekday() {
+        // Second Sunday in March, first Sunday in November
+        assert_eq!(
+            PosixDay::MonthWeekday(3, 2, 0).date(2024),
+            date(2024, 3, 10)
+        );
+        assert_eq!(
+            PosixDay::MonthWeekday(11, 1, 0).date(2024),
+            date(2024, 11, 3)
+        );
+        // Last Sunday of the month
+        assert_eq!(
+            PosixDay::MonthWeekday(3, 5, 0).date(2024),
+            date(2024, 3, 31)
+        );
+        assert_eq!(
+            PosixDay::MonthWeekday(10, 5, 0).date(2024),
+            date(2024, 10, 27)
+        );
+        assert_eq!(
+            PosixDay::MonthWeekday(2, 5, 4).date(2024),
+            date(2024, 2, 29)
+        );
+    }
+
+    #[test]
+    fn julian_days() {
+        assert_eq!(PosixDay::Julian(59).date(2024), date(2024, 2, 28));
+        assert_eq!(PosixDay::Julian(60).date(2024), date(2024, 3, 1));
+        assert_eq!(PosixDay::Julian(60).date(2023), date(2023, 3, 1));
+        assert_eq!(PosixDay::Zero(59).date(2024), date(2024, 2, 29));
+        assert_eq!(PosixDay::Zero(59).date(2023), date(2023, 3, 1));
+        assert_eq!(PosixDay::Zero(365).date(2024), date(2024, 12, 31));
+        assert_eq!(PosixDay::Zero(365).date(2023), date(2024, 1, 1));
+
+        // Ending DST on day 365 still does so in years without February 29.
+        let tz: PosixTimeZone = "EST5EDT,M3.2.0,365".parse().unwrap();
+        for year in [2023, 2024] {
+            let summer = tz.with_ymd_and_hms(year, 7, 1, 12, 0, 0).unwrap();
+            assert_eq!(summer.offset().abbreviation(), "EDT", "{year}");
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let tz = PosixTz::parse("EST5EDT,M3.2.0,M11.1.0").unwrap();
+        assert_eq!(tz.std.name, "EST");
+        assert_eq!(tz.std.offset, -5 * 3600);
+        let dst = tz.dst.unwrap();
+        assert_eq!(dst.timespan.name, "EDT");
+        assert_eq!(dst.timespan.offset, -4 * 3600);
+        assert_eq!(dst.start.day, PosixDay::MonthWeekday(3, 2, 0));
+        assert_eq!(dst.end.time, 7200);
+
+        let tz = PosixTz::parse("<+0330>-3:30").unwrap();
+        assert_eq!(tz.std.name, "+0330");
+        assert_eq!(tz.std.offset, 3 * 3600 + 30 * 60);
+        assert!(tz.dst.is_none());
+
+        let tz = PosixTz::parse("IST-1GMT0,M10.5.0,M3.5.0/1").unwrap();
+        assert_eq!(tz.std.offset, 3600);
+        assert_eq!(tz.dst.unwrap().timespan.offset, 0);
+
+        // Extensions from RFC 8536: negative and large transition times, all-year DST
+        let tz = PosixTz::parse("<-03>3<-02>,M3.5.0/-2,M10.5.0/-1").unwrap();
+        assert_eq!(tz.dst.unwrap().start.time, -7200);
+        let tz = PosixTz::parse("EST5EDT,0/0,J365/25").unwrap();
+        assert_eq!(tz.dst.unwrap().start.day, PosixDay::Zero(0));
+        assert_eq!(tz.dst.unwrap().end.day, PosixDay::Julian(365));
+        assert_eq!(tz.dst.unwrap().end.time, 25 * 3600);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for tz in [
+            "",
+            "EST",
+            "ES5",
+            "<+03",
+            "EST5EDT,M3.2.0",
+            "EST5EDT,M13.2.0,M11.1.0",
+            "EST5EDT,M3.2.0,M11.1.0,",
+            "EST25",
+            "EST24",
+            "EST-24",
+            "EST5EDT-24",
+            "<+2330>-23:30<+2430>",
+        ] {
+            assert!(PosixTz::parse(tz).is_none(), "{tz}");
+        }
+    }
+
+    #[test]
+    fn southern_hemisphere() {
+        // AEST-10AEDT,M10.1.0,M4.1.0/3
+        let aest = FixedTimespan {
+            utc_offset: 36000,
+            dst_offset: 0,
+            name: "AEST",
+        };
+        let aedt = FixedTimespan {
+            utc_offset: 36000,
+            dst_offset: 3600,
+            name: "AEDT",
+        };
+        let tz = PosixTz {
+            std: aest,
+            dst: Some(PosixDst {
+                timespan: aedt,
+                start: PosixRule {
+                    day: PosixDay::MonthWeekday(10, 1, 0),
+                    time: 7200,
+                },
+                end: PosixRule {
+                    day: PosixDay::MonthWeekday(4, 1, 0),
+                    time: 10800,
+                },
+            }),
+        };
+
+        // 2200-01-15, 2200-07-15 and 2200-12-15 at midnight UTC
+        assert_eq!(tz.timespan_at(7259328000), aedt);
+        assert_eq!(tz.timespan_at(7274966400), aest);
+        assert_eq!(tz.timespan_at(7288185600), aedt);
+    }
+
+    #[test]
+    fn transitions_across_years() {
+        // Daylight saving time starts two hours before the year does, at 22:00 on December 31st.
+        let tz = PosixTz::parse("<-03>3<-02>,J1/-2,J182/0").unwrap();
+        let start = Utc
+            .with_ymd_and_hms(2024, 1, 1, 1, 0, 0)
+            .unwrap()
+            .timestamp();
+        assert_eq!(tz.timespan_at(start - 1).name, "-03");
+        assert_eq!(tz.timespan_at(start).name, "-02");
+        assert_eq!(tz.timespan_at(start + 1800).name, "-02");
+        assert_eq!(tz.count_transitions(start - 1, start), 1);
+        assert_eq!(tz.count_transitions(start - 1, start + 1800), 1);
+        let (at, before, after) = tz.nth_transition(start - 1, 0).unwrap();
+        assert_eq!((at, before.name, after.name), (start, "-03", "-02"));
+        let (at, ..) = tz.nth_transition(start - 1, 2).unwrap();
+        assert_eq!(at, start + 366 * 86400);
+
+        // And ends two hours after it does, at 02:00 EDT on January 1st.
+        let tz = PosixTz::parse("EST5EDT,J100,J365/26").unwrap();
+        let end = Utc
+            .with_ymd_and_hms(2024, 1, 1, 6, 0, 0)
+            .unwrap()
+            .timestamp();
+        assert_eq!(tz.timespan_at(end - 1800).name, "EDT");
+        assert_eq!(tz.timespan_at(end).name, "EST");
+        assert_eq!(tz.count_transitions(end - 1800, end), 1);
+        assert_eq!(tz.count_transitions(end, end + 1800), 0);
+        let (at, before, after) = tz.nth_transition(end - 1800, 0).unwrap();
+        assert_eq!((at, before.name, after.name), (end, "EDT", "EST"));
+
+        let tz: PosixTimeZone = "EST5EDT,J100,J365/26".parse().unwrap();
+        let local = tz.with_ymd_and_hms(2024, 1, 1, 0, 30, 0).unwrap();
+        assert_eq!(local.offset().abbreviation(), "EDT");
+    }
+
+    #[test]
+    fn posix_time_zone() {
+        let tz: PosixTimeZone = "EST5EDT,M3.2.0/2,M11.1.0/2".parse().unwrap();
+
+        let winter = tz.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+        assert_eq!(winter.offset().abbreviation(), "EST");
+        assert_eq!(winter.offset().fix().local_minus_utc(), -5 * 3600);
+        assert!(!winter.offset().is_dst());
+
+        let summer = tz.with_ymd_and_hms(2024, 7, 15, 12, 0, 0).unwrap();
+        assert_eq!(summer.offset().abbreviation(), "EDT");
+        assert_eq!(summer.offset().base_utc_offset(), Duration::hours(-5));
+        assert_eq!(summer.offset().dst_offset(), Duration::hours(1));
+        assert_eq!(summer.offset().tz_id(), "");
+
+        assert_eq!(
+            tz.with_ymd_and_hms(2024, 3, 10, 2, 30, 0),
+            LocalResult::None
+        );
+        let LocalResult::Ambiguous(earliest, latest) = tz.with_ymd_and_hms(2024, 11, 3, 1, 30, 0)
+        else {
+            panic!("expected an ambiguous time");
+        };
+        assert_eq!(earliest.offset().abbreviation(), "EDT");
+        assert_eq!(latest.offset().abbreviation(), "EST");
+
+        let utc = Utc.with_ymd_and_hms(2024, 3, 10, 7, 0, 0).unwrap();
+        assert_eq!(utc.with_timezone(&tz).offset().abbreviation(), "EDT");
+        let utc = utc - Duration::seconds(1);
+        assert_eq!(utc.with_timezone(&tz).offset().abbreviation(), "EST");
+    }
+
+    #[test]
+    fn posix_time_zone_display() {
+        for tz in [
+            "EST5EDT,M3.2.0,M11.1.0",
+            "<+0330>-3:30",
+            "IST-1GMT0,M10.5.0,M3.5.0/1",
+            "<-03>3<-02>,M3.5.0/-2,M10.5.0/-1",
+            "AEST-10AEDT,M10.1.0,M4.1.0/3",
+            "XXX3:25:45YYY,J60/1:30,300",
+        ] {
+            assert_eq!(tz.parse::<PosixTimeZone>().unwrap().to_string(), tz);
+        }
+
+        let tz: PosixTimeZone = "EST+5EDT+4:00,M3.2.0/2:00,M11.1.0/02".parse().unwrap();
+        assert_eq!(tz.to_string(), "EST5EDT,M3.2.0,M11.1.0");
+        let tz: PosixTimeZone = "CET-1CEST".parse().unwrap();
+        assert_eq!(tz.to_string(), "CET-1CEST,M3.2.0,M11.1.0");
+    }
+
+    #[test]
+    fn posix_time_zone_invalid() {
+        assert!(":America/New_York".parse::<PosixTimeZone>().is_err());
+        assert!("America/New_York".parse::<PosixTimeZone>().is_err());
+        assert!("AAA24".parse::<PosixTimeZone>().is_err());
+        assert!("AAA23:59:59".parse::<PosixTimeZone>().is_ok());
+        assert!("<ABCDEFGHIJKLMNOP>0".parse::<PosixTimeZone>().is_err());
+        assert!("<ABCDEFGHIJKLMNO>0".parse::<PosixTimeZone>().is_ok());
+    }
+}
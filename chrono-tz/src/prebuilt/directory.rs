@@ -1,5 +1,7 @@
 pub const IANA_TZDB_VERSION: &str = "2025b";
 
+pub const IANA_TZDB_DATA_FORM: &str = "main";
+
 use crate::timezones::Tz;
 
 pub const CET: Tz = Tz::CET;
@@ -5,8 +5,8 @@
     non_upper_case_globals
 )]
 
-#[cfg(not(any(feature = "case-insensitive", feature = "filter-by-regex")))]
+#[cfg(not(chrono_tz_codegen))]
 pub(crate) mod directory;
-#[cfg(not(any(feature = "case-insensitive", feature = "filter-by-regex")))]
+#[cfg(not(chrono_tz_codegen))]
 #[rustfmt::skip]
 pub(crate) mod timezones;
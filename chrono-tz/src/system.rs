@@ -0,0 +1,197 @@
+use core::fmt::{self, Display, Formatter};
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::{Component, Path};
+
+use crate::timezones::Tz;
+
+impl Tz {
+    /// Returns the timezone the system is configured with
+    ///
+    /// The following sources are tried in order:
+    ///
+    /// - the `TZ` environment variable, either as a zone name such as `Europe/Paris`, a name
+    ///   prefixed with a colon such as `:Europe/Paris`, or the path of a zone file;
+    /// - the target of the `/etc/localtime` symlink, such as
+    ///   `/usr/share/zoneinfo/Europe/Paris`;
+    /// - the contents of `/etc/timezone`.
+    ///
+    /// If `TZ` is set, it alone decides the zone, just like it does for the C library.
+    ///
+    /// Unlike `chrono::Local`, which only knows the current offset, this keeps the identity of
+    /// the zone, so that it can be used for conversions at any instant and in
+    /// [`OffsetName::tz_id`](crate::OffsetName::tz_id).
+    pub fn system() -> Result<Tz, SystemTzError> {
+        let tz = env::var("TZ").ok();
+        let localtime = fs::read_link("/etc/localtime").ok();
+        let timezone = fs::read_to_string("/etc/timezone").ok();
+        resolve(tz.as_deref(), localtime.as_deref(), timezone.as_deref())
+    }
+}
+
+/// Returns the timezone the system is configured with
+///
+/// This is a shorthand for [`Tz::system`].
+pub fn local_tz() -> Result<Tz, SystemTzError> {
+    Tz::system()
+}
+
+fn resolve(
+    tz: Option<&str>,
+    localtime: Option<&Path>,
+    timezone: Option<&str>,
+) -> Result<Tz, SystemTzError> {
+    if let Some(tz) = tz.filter(|tz| !tz.is_empty()) {
+        let name = tz.strip_prefix(':').unwrap_or(tz);
+        return match name.starts_with('/') {
+            true => from_path(Path::new(name)),
+            false => name.parse().ok(),
+        }
+        .ok_or_else(|| SystemTzError::UnknownZone(tz.to_owned()));
+    }
+
+    if let Some(localtime) = localtime {
+        return from_path(localtime)
+            .ok_or_else(|| SystemTzError::UnknownZone(localtime.display().to_string()));
+    }
+
+    if let Some(timezone) = timezone.map(str::trim).filter(|name| !name.is_empty()) {
+        return timezone
+            .parse()
+            .map_err(|_| SystemTzError::UnknownZone(timezone.to_owned()));
+    }
+
+    Err(SystemTzError::NotConfigured)
+}
+
+/// Finds the zone a zone file path refers to, such as `Europe/Paris` for
+/// `/usr/share/zoneinfo/Europe/Paris`
+fn from_path(path: &Path) -> Option<Tz> {
+    let components = path
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let mut name = match components.iter().rposition(|c| *c == "zoneinfo") {
+        Some(index) => &components[index + 1..],
+        // Without a zoneinfo directory to go by, take the longest suffix that is a zone name.
+        None => {
+            return (0..components.len())
+                .find_map(|start| components[start..].join("/").parse().ok());
+        }
+    };
+    // The `posix` and `right` trees hold the same zones, without and with leap seconds.
+    if let Some((&("posix" | "right"), rest)) = name.split_first() {
+        name = rest;
+    }
+    name.join("/").parse().ok()
+}
+
+/// An error returned when the timezone of the system can't be determined
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SystemTzError {
+    /// The configured zone, or the path of its file, doesn't match any zone in the database
+    UnknownZone(String),
+    /// Neither the `TZ` environment variable, nor `/etc/localtime` or `/etc/timezone` is set
+    NotConfigured,
+}
+
+impl Display for SystemTzError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SystemTzError::UnknownZone(name) => write!(f, "unknown system timezone `{name}`"),
+            SystemTzError::NotConfigured => f.write_str("system timezone is not configured"),
+        }
+    }
+}
+
+impl Error for SystemTzError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{resolve, SystemTzError};
+    use crate::Tz;
+    use std::path::Path;
+
+    #[test]
+    fn tz_variable() {
+        let localtime = Path::new("/usr/share/zoneinfo/Asia/Tokyo");
+        assert_eq!(
+            resolve(Some("Europe/Paris"), None, None),
+            Ok(Tz::Europe__Paris)
+        );
+        assert_eq!(
+            resolve(Some(":Europe/Paris"), Some(localtime), None),
+            Ok(Tz::Europe__Paris)
+        );
+        assert_eq!(
+            resolve(Some(":/usr/share/zoneinfo/Europe/Paris"), None, None),
+            Ok(Tz::Europe__Paris)
+        );
+        // An empty `TZ` is the same as an unset one.
+        assert_eq!(
+            resolve(Some(""), Some(localtime), None),
+            Ok(Tz::Asia__Tokyo)
+        );
+        assert_eq!(
+            resolve(Some("CET-1CEST,M3.5.0,M10.5.0/3"), Some(localtime), None),
+            Err(SystemTzError::UnknownZone(
+                "CET-1CEST,M3.5.0,M10.5.0/3".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn localtime_symlink() {
+        for (target, tz) in [
+            (
+                "/usr/share/zoneinfo/America/New_York",
+                Tz::America__New_York,
+            ),
+            (
+                "../usr/share/zoneinfo/America/New_York",
+                Tz::America__New_York,
+            ),
+            (
+                "/var/db/timezone/zoneinfo/Europe/Berlin",
+                Tz::Europe__Berlin,
+            ),
+            (
+                "/usr/share/zoneinfo/posix/Europe/Berlin",
+                Tz::Europe__Berlin,
+            ),
+            ("/usr/share/zoneinfo/UTC", Tz::UTC),
+            (
+                "/etc/zones/America/Argentina/Buenos_Aires",
+                Tz::America__Argentina__Buenos_Aires,
+            ),
+        ] {
+            assert_eq!(
+                resolve(None, Some(Path::new(target)), Some("Asia/Tokyo")),
+                Ok(tz)
+            );
+        }
+
+        assert!(matches!(
+            resolve(None, Some(Path::new("/etc/localtime.backup")), None),
+            Err(SystemTzError::UnknownZone(_))
+        ));
+    }
+
+    #[test]
+    fn timezone_file() {
+        assert_eq!(
+            resolve(None, None, Some("Asia/Tokyo\n")),
+            Ok(Tz::Asia__Tokyo)
+        );
+        assert_eq!(
+            resolve(None, None, Some("\n")),
+            Err(SystemTzError::NotConfigured)
+        );
+        assert_eq!(resolve(None, None, None), Err(SystemTzError::NotConfigured));
+    }
+}
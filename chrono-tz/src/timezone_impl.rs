@@ -1,12 +1,12 @@
-use core::cmp::Ordering;
 use core::fmt::{Debug, Display, Error, Formatter};
 
 use chrono::{
-    DateTime, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone,
+    DateTime, Duration, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeZone,
 };
 
-use crate::binary_search::binary_search;
-use crate::timezones::Tz;
+use crate::posix::PosixTz;
+use crate::timezones::{Tz, TIMESPANS};
 
 /// An Offset that applies for a period of time
 ///
@@ -15,11 +15,20 @@ use crate::timezones::Tz;
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub struct FixedTimespan {
     /// The base offset from UTC; this usually doesn't change unless the government changes something
-    pub offset: i32,
+    pub utc_offset: i32,
+    /// The additional offset from UTC for this timespan; typically for daylight saving time
+    pub dst_offset: i32,
     /// The name of this timezone, for example the difference between `EDT`/`EST`
     pub name: &'static str,
 }
 
+impl FixedTimespan {
+    /// The total offset from UTC in effect during this timespan
+    pub(crate) fn offset(&self) -> i32 {
+        self.utc_offset + self.dst_offset
+    }
+}
+
 impl Display for FixedTimespan {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
         write!(f, "{}", self.name)
@@ -74,8 +83,53 @@ pub trait OffsetName {
     fn abbreviation(&self) -> &str;
 }
 
+/// Detailed timezone offset components that expose any special conditions currently in effect.
+///
+/// This trait breaks down an offset into the standard UTC offset and any special offset
+/// in effect (such as DST) at a given time.
+///
+/// ```
+/// # extern crate chrono;
+/// # extern crate chrono_tz;
+/// use chrono::{Duration, Offset, TimeZone};
+/// use chrono_tz::Europe::London;
+/// use chrono_tz::OffsetComponents;
+///
+/// # fn main() {
+/// let london_time = London.ymd(2016, 5, 10).and_hms(12, 0, 0);
+///
+/// // London typically has zero offset from UTC, but has a 1h adjustment forward
+/// // when summer time is in effect.
+/// let lon_utc_offset = london_time.offset().base_utc_offset();
+/// let lon_dst_offset = london_time.offset().dst_offset();
+/// let total_offset = lon_utc_offset + lon_dst_offset;
+/// assert_eq!(lon_utc_offset, Duration::hours(0));
+/// assert_eq!(lon_dst_offset, Duration::hours(1));
+/// assert!(london_time.offset().is_dst());
+///
+/// // As a sanity check, make sure that the total offsets added together are equivalent to the
+/// // total fixed offset.
+/// assert_eq!(total_offset.num_seconds(), london_time.offset().fix().local_minus_utc() as i64);
+/// # }
+/// ```
+pub trait OffsetComponents {
+    /// The base offset from UTC; this usually doesn't change unless the government changes something
+    fn base_utc_offset(&self) -> Duration;
+    /// The additional offset from UTC that is currently in effect; typically for daylight saving time
+    ///
+    /// This can be negative: the tz database has Irish Standard Time as the standard time of
+    /// `Europe/Dublin`, and winter time as one hour of negative daylight saving time.
+    fn dst_offset(&self) -> Duration;
+    /// Whether daylight saving time (or any other special offset) is currently in effect
+    ///
+    /// This is true whenever [`dst_offset`](Self::dst_offset) is not zero, including when it's
+    /// negative, so it's true in winter in `Europe/Dublin` unless the timezones were built from
+    /// the rearguard form of the data (see the `data-form` feature).
+    fn is_dst(&self) -> bool;
+}
+
 impl TzOffset {
-    fn new(tz: Tz, offset: FixedTimespan) -> Self {
+    pub(crate) fn new(tz: Tz, offset: FixedTimespan) -> Self {
         TzOffset { tz, offset }
     }
 
@@ -100,9 +154,23 @@ impl OffsetName for TzOffset {
     }
 }
 
+impl OffsetComponents for TzOffset {
+    fn base_utc_offset(&self) -> Duration {
+        Duration::seconds(self.offset.utc_offset as i64)
+    }
+
+    fn dst_offset(&self) -> Duration {
+        Duration::seconds(self.offset.dst_offset as i64)
+    }
+
+    fn is_dst(&self) -> bool {
+        self.offset.dst_offset != 0
+    }
+}
+
 impl Offset for TzOffset {
     fn fix(&self) -> FixedOffset {
-        FixedOffset::east_opt(self.offset.offset).unwrap()
+        FixedOffset::east_opt(self.offset.offset()).unwrap()
     }
 }
 
@@ -143,74 +211,262 @@ impl Span {
         }
     }
 
-    fn cmp(&self, x: i64) -> Ordering {
-        match (self.begin, self.end) {
-            (Some(a), Some(b)) if a <= x && x < b => Ordering::Equal,
-            (Some(a), Some(b)) if a <= x && b <= x => Ordering::Less,
-            (Some(_), Some(_)) => Ordering::Greater,
-            (Some(a), None) if a <= x => Ordering::Equal,
-            (Some(_), None) => Ordering::Greater,
-            (None, Some(b)) if b <= x => Ordering::Less,
-            (None, Some(_)) => Ordering::Equal,
-            (None, None) => Ordering::Equal,
+    /// The same span in the local time of the timespan in effect during it
+    fn local(&self, timespan: FixedTimespan) -> Span {
+        let offset = timespan.offset() as i64;
+        Span {
+            begin: self.begin.map(|begin| begin + offset),
+            end: self.end.map(|end| end + offset),
         }
     }
 }
 
+/// The number of transitions in each block of [`FixedTimespanSet::rest`]
+///
+/// This has to match the blocks chrono-tz-build writes.
+const BLOCK_LEN: usize = 8;
+
+/// The timespans of a zone, in the packed form the generated code stores them in
+///
+/// A zone only uses a handful of the distinct timespans in `TIMESPANS`, so `types` lists
+/// their indices there, with the one in effect before the first transition coming first.
+/// `rest` then holds the transitions in chronological order, each as the number of seconds
+/// since the previous one as a zigzag-encoded LEB128 varint, followed by a byte with the
+/// position in `types` of the timespan that takes effect.
+///
+/// To look transitions up without decoding everything before them, they're split in blocks of
+/// [`BLOCK_LEN`]. `blocks` holds the UTC timestamp of the first transition of each block, and
+/// `offsets` where the block starts in `rest`. Since its time is in `blocks`, the first
+/// transition of a block only has its timespan in `rest`.
 #[derive(Copy, Clone)]
 pub struct FixedTimespanSet {
-    pub first: FixedTimespan,
-    pub rest: &'static [(i64, FixedTimespan)],
+    pub types: &'static [u16],
+    pub rest: &'static [u8],
+    pub blocks: &'static [i64],
+    pub offsets: &'static [u16],
+    /// The recurring rules that take over after the last transition in `rest`
+    pub footer: Option<PosixTz>,
 }
 
 impl FixedTimespanSet {
-    fn len(&self) -> usize {
-        1 + self.rest.len()
+    fn timespan(&self, index: u8) -> FixedTimespan {
+        TIMESPANS[self.types[index as usize] as usize]
     }
 
-    fn utc_span(&self, index: usize) -> Span {
-        debug_assert!(index < self.len());
-        Span {
-            begin: if index == 0 {
-                None
-            } else {
-                Some(self.rest[index - 1].0)
-            },
-            end: if index == self.rest.len() {
-                None
-            } else {
-                Some(self.rest[index].0)
-            },
+    /// The transitions in `rest` from the start of the given block on
+    fn rest_from(&self, block: usize) -> Rest {
+        let offset = self
+            .offsets
+            .get(block)
+            .map_or(self.rest.len(), |&offset| offset as usize);
+        Rest {
+            blocks: self.blocks,
+            bytes: &self.rest[offset..],
+            index: block * BLOCK_LEN,
+            start: 0,
         }
     }
 
-    fn local_span(&self, index: usize) -> Span {
-        debug_assert!(index < self.len());
-        Span {
-            begin: if index == 0 {
-                None
-            } else {
-                let span = self.rest[index - 1];
-                Some(span.0 + span.1.offset as i64)
-            },
-            end: if index == self.rest.len() {
-                None
-            } else if index == 0 {
-                Some(self.rest[index].0 + self.first.offset as i64)
-            } else {
-                Some(self.rest[index].0 + self.rest[index - 1].1.offset as i64)
+    /// The spans of time between the transitions in `rest`, in chronological order, from the
+    /// one in effect at the given UTC timestamp or before on
+    fn spans_from(&self, timestamp: i64) -> Spans {
+        // The number of blocks whose first transition happens at or before the timestamp
+        let blocks = self.blocks.partition_point(|&start| start <= timestamp);
+        match blocks.checked_sub(1) {
+            Some(block) => {
+                let mut rest = self.rest_from(block);
+                let (start, index) = rest.next().unwrap();
+                Spans {
+                    rest,
+                    begin: Some(start),
+                    index: Some(index),
+                }
+            }
+            None => Spans {
+                rest: self.rest_from(0),
+                begin: None,
+                index: Some(0),
             },
         }
     }
 
-    fn get(&self, index: usize) -> FixedTimespan {
-        debug_assert!(index < self.len());
-        if index == 0 {
-            self.first
-        } else {
-            self.rest[index - 1].1
+    /// The spans of time between the transitions in `rest`, in chronological order, from one
+    /// that ends before the given local timestamp or the first on
+    fn spans_from_local(&self, timestamp: i64) -> Spans {
+        // No offset is anywhere near a day, let alone two.
+        self.spans_from(timestamp.saturating_sub(2 * 86_400))
+    }
+
+    /// The number of transitions in `rest` along with the UTC timestamp of the last one
+    fn last_transition(&self) -> Option<(usize, i64)> {
+        let block = self.blocks.len().checked_sub(1)?;
+        let mut rest = self.rest_from(block);
+        let (start, _) = rest.by_ref().last()?;
+        Some((rest.index, start))
+    }
+
+    /// Returns the footer if it decides the offset in effect at the given UTC timestamp
+    fn footer_at(&self, timestamp: i64) -> Option<&PosixTz> {
+        let footer = self.footer.as_ref()?;
+        // There's no need to decode the last block for a timestamp before it.
+        match self.blocks.last() {
+            Some(&start) if timestamp < start => return None,
+            _ => {}
+        }
+        match self.last_transition() {
+            Some((_, start)) if timestamp < start => None,
+            _ => Some(footer),
+        }
+    }
+
+    /// Returns the footer if it decides the offsets possible at the given local timestamp
+    fn footer_at_local(&self, timestamp: i64) -> Option<&PosixTz> {
+        let footer = self.footer.as_ref()?;
+        self.footer_at(timestamp - footer.max_offset() as i64)
+    }
+
+    /// The timespan in effect at the given UTC timestamp
+    fn timespan_at(&self, timestamp: i64) -> FixedTimespan {
+        // Any timestamp falls within exactly one span, the last of which never ends.
+        let (span, index) = self
+            .spans_from(timestamp)
+            .find(|(span, _)| span.end.map_or(true, |end| timestamp < end))
+            .unwrap();
+        match (&self.footer, span.end) {
+            (Some(footer), None) => footer.timespan_at(timestamp),
+            _ => self.timespan(index),
         }
     }
+
+    /// The timespans in effect at the given local timestamp, which may be none or two of them
+    /// around a transition
+    fn local_timespans(&self, timestamp: i64) -> LocalResult<FixedTimespan> {
+        if let Some(footer) = self.footer_at_local(timestamp) {
+            return footer.local_timespans(timestamp);
+        }
+
+        let mut result = LocalResult::None;
+        for (span, index) in self.spans_from_local(timestamp) {
+            let timespan = self.timespan(index);
+            let span = span.local(timespan);
+            if span.begin.map_or(false, |begin| timestamp < begin) {
+                break;
+            }
+            if span.contains(timestamp) {
+                result = match result {
+                    LocalResult::None => LocalResult::Single(timespan),
+                    LocalResult::Single(earlier) => LocalResult::Ambiguous(earlier, timespan),
+                    ambiguous => ambiguous,
+                };
+            }
+        }
+        result
+    }
+
+    /// The number of transitions that happen at or before the given UTC timestamp, including
+    /// the ones the footer keeps generating after the last transition in `rest`
+    pub(crate) fn transitions_until(&self, timestamp: i64) -> usize {
+        let blocks = self.blocks.partition_point(|&start| start <= timestamp);
+        let block = blocks.saturating_sub(1);
+        let count = block * BLOCK_LEN
+            + self
+                .rest_from(block)
+                .take_while(|&(start, _)| start <= timestamp)
+                .count();
+        match (&self.footer, self.last_transition()) {
+            (Some(footer), Some((_, last))) => count + footer.count_transitions(last, timestamp),
+            _ => count,
+        }
+    }
+
+    /// The transition at the given index, as its UTC timestamp along with the timespans in
+    /// effect before and after it
+    pub(crate) fn transition(&self, index: usize) -> Option<(i64, FixedTimespan, FixedTimespan)> {
+        let (len, last) = self.last_transition()?;
+        if index >= len {
+            return self.footer.as_ref()?.nth_transition(last, index - len);
+        }
+
+        let nth = |index: usize| self.rest_from(index / BLOCK_LEN).nth(index % BLOCK_LEN);
+        let before = match index.checked_sub(1) {
+            Some(previous) => nth(previous)?.1,
+            None => 0,
+        };
+        let (start, after) = nth(index)?;
+        Some((start, self.timespan(before), self.timespan(after)))
+    }
+}
+
+/// An iterator decoding the transitions of a [`FixedTimespanSet`], as their UTC timestamps
+/// along with the position in `types` of the timespan that takes effect at each
+struct Rest {
+    blocks: &'static [i64],
+    bytes: &'static [u8],
+    /// The index of the next transition
+    index: usize,
+    /// The UTC timestamp of the last transition, which the next one is relative to
+    start: i64,
+}
+
+impl Rest {
+    fn byte(&mut self) -> u8 {
+        let (&byte, bytes) = self.bytes.split_first().unwrap();
+        self.bytes = bytes;
+        byte
+    }
+
+    fn varint(&mut self) -> i64 {
+        let mut value = 0u64;
+        let mut shift = 0;
+        loop {
+            let byte = self.byte();
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+            shift += 7;
+        }
+        (value >> 1) as i64 ^ -((value & 1) as i64)
+    }
+}
+
+impl Iterator for Rest {
+    type Item = (i64, u8);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+        self.start = match self.index % BLOCK_LEN {
+            0 => self.blocks[self.index / BLOCK_LEN],
+            _ => self.start + self.varint(),
+        };
+        self.index += 1;
+        Some((self.start, self.byte()))
+    }
+}
+
+/// An iterator over the spans of time of a [`FixedTimespanSet`], along with the position in
+/// `types` of the timespan in effect during each
+struct Spans {
+    rest: Rest,
+    begin: Option<i64>,
+    index: Option<u8>,
+}
+
+impl Iterator for Spans {
+    type Item = (Span, u8);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.index.take()?;
+        let begin = self.begin;
+        let end = self.rest.next().map(|(start, next)| {
+            self.begin = Some(start);
+            self.index = Some(next);
+            start
+        });
+        Some((Span { begin, end }, index))
+    }
 }
 
 pub trait TimeSpans {
@@ -226,72 +482,14 @@ impl TimeZone for Tz {
 
     #[allow(deprecated)]
     fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<Self::Offset> {
-        let earliest = self.offset_from_local_datetime(&local.and_time(NaiveTime::MIN));
-        let latest = self.offset_from_local_datetime(&local.and_hms_opt(23, 59, 59).unwrap());
-        // From the chrono docs:
-        //
-        // > This type should be considered ambiguous at best, due to the inherent lack of
-        // > precision required for the time zone resolution. There are some guarantees on the usage
-        // > of `Date<Tz>`:
-        // > - If properly constructed via `TimeZone::ymd` and others without an error,
-        // >   the corresponding local date should exist for at least a moment.
-        // >   (It may still have a gap from the offset changes.)
-        //
-        // > - The `TimeZone` is free to assign *any* `Offset` to the local date,
-        // >   as long as that offset did occur in given day.
-        // >   For example, if `2015-03-08T01:59-08:00` is followed by `2015-03-08T03:00-07:00`,
-        // >   it may produce either `2015-03-08-08:00` or `2015-03-08-07:00`
-        // >   but *not* `2015-03-08+00:00` and others.
-        //
-        // > - Once constructed as a full `DateTime`,
-        // >   `DateTime::date` and other associated methods should return those for the original `Date`.
-        // >   For example, if `dt = tz.ymd(y,m,d).hms(h,n,s)` were valid, `dt.date() == tz.ymd(y,m,d)`.
-        //
-        // > - The date is timezone-agnostic up to one day (i.e. practically always),
-        // >   so the local date and UTC date should be equal for most cases
-        // >   even though the raw calculation between `NaiveDate` and `Duration` may not.
-        //
-        // For these reasons we return always a single offset here if we can, rather than being
-        // technically correct and returning Ambiguous(_,_) on days when the clock changes. The
-        // alternative is painful errors when computing unambiguous times such as
-        // `TimeZone.ymd(ambiguous_date).hms(unambiguous_time)`.
-        use chrono::LocalResult::*;
-        match (earliest, latest) {
-            (result @ Single(_), _) => result,
-            (_, result @ Single(_)) => result,
-            (Ambiguous(offset, _), _) => Single(offset),
-            (_, Ambiguous(offset, _)) => Single(offset),
-            (None, None) => None,
-        }
+        offset_from_local_date(self, local)
     }
 
-    // First search for a timespan that the local datetime falls into, then, if it exists,
-    // check the two surrounding timespans (if they exist) to see if there is any ambiguity.
+    // Look for the timespans whose local span the datetime falls into. There are two of them
+    // when it's ambiguous, and none when it's in a gap.
     fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<Self::Offset> {
         let timestamp = local.and_utc().timestamp();
-        let timespans = self.timespans();
-        let index = binary_search(0, timespans.len(), |i| {
-            timespans.local_span(i).cmp(timestamp)
-        });
-        TzOffset::map_localresult(
-            *self,
-            match index {
-                Ok(0) if timespans.len() == 1 => LocalResult::Single(timespans.get(0)),
-                Ok(0) if timespans.local_span(1).contains(timestamp) => {
-                    LocalResult::Ambiguous(timespans.get(0), timespans.get(1))
-                }
-                Ok(0) => LocalResult::Single(timespans.get(0)),
-                Ok(i) if timespans.local_span(i - 1).contains(timestamp) => {
-                    LocalResult::Ambiguous(timespans.get(i - 1), timespans.get(i))
-                }
-                Ok(i) if i == timespans.len() - 1 => LocalResult::Single(timespans.get(i)),
-                Ok(i) if timespans.local_span(i + 1).contains(timestamp) => {
-                    LocalResult::Ambiguous(timespans.get(i), timespans.get(i + 1))
-                }
-                Ok(i) => LocalResult::Single(timespans.get(i)),
-                Err(_) => LocalResult::None,
-            },
-        )
+        TzOffset::map_localresult(*self, self.timespans().local_timespans(timestamp))
     }
 
     #[allow(deprecated)]
@@ -300,14 +498,55 @@ impl TimeZone for Tz {
         self.offset_from_utc_datetime(&utc.and_time(NaiveTime::MIN))
     }
 
-    // Binary search for the required timespan. Any i64 is guaranteed to fall within
-    // exactly one timespan, no matter what (so the `unwrap` is safe).
+    // Any i64 is guaranteed to fall within exactly one timespan, no matter what. Past the last
+    // transition, the recurring rules of the footer take over if there are any.
     fn offset_from_utc_datetime(&self, dt: &NaiveDateTime) -> Self::Offset {
         let timestamp = dt.and_utc().timestamp();
-        let timespans = self.timespans();
-        let index =
-            binary_search(0, timespans.len(), |i| timespans.utc_span(i).cmp(timestamp)).unwrap();
-        TzOffset::new(*self, timespans.get(index))
+        TzOffset::new(*self, self.timespans().timespan_at(timestamp))
+    }
+}
+
+/// Picks an offset for a local date, shared by all the `TimeZone` implementations of this crate
+pub(crate) fn offset_from_local_date<T: TimeZone>(
+    tz: &T,
+    local: &NaiveDate,
+) -> LocalResult<T::Offset> {
+    let earliest = tz.offset_from_local_datetime(&local.and_time(NaiveTime::MIN));
+    let latest = tz.offset_from_local_datetime(&local.and_hms_opt(23, 59, 59).unwrap());
+    // From the chrono docs:
+    //
+    // > This type should be considered ambiguous at best, due to the inherent lack of
+    // > precision required for the time zone resolution. There are some guarantees on the usage
+    // > of `Date<Tz>`:
+    // > - If properly constructed via `TimeZone::ymd` and others without an error,
+    // >   the corresponding local date should exist for at least a moment.
+    // >   (It may still have a gap from the offset changes.)
+    //
+    // > - The `TimeZone` is free to assign *any* `Offset` to the local date,
+    // >   as long as that offset did occur in given day.
+    // >   For example, if `2015-03-08T01:59-08:00` is followed by `2015-03-08T03:00-07:00`,
+    // >   it may produce either `2015-03-08-08:00` or `2015-03-08-07:00`
+    // >   but *not* `2015-03-08+00:00` and others.
+    //
+    // > - Once constructed as a full `DateTime`,
+    // >   `DateTime::date` and other associated methods should return those for the original `Date`.
+    // >   For example, if `dt = tz.ymd(y,m,d).hms(h,n,s)` were valid, `dt.date() == tz.ymd(y,m,d)`.
+    //
+    // > - The date is timezone-agnostic up to one day (i.e. practically always),
+    // >   so the local date and UTC date should be equal for most cases
+    // >   even though the raw calculation between `NaiveDate` and `Duration` may not.
+    //
+    // For these reasons we return always a single offset here if we can, rather than being
+    // technically correct and returning Ambiguous(_,_) on days when the clock changes. The
+    // alternative is painful errors when computing unambiguous times such as
+    // `TimeZone.ymd(ambiguous_date).hms(unambiguous_time)`.
+    use chrono::LocalResult::*;
+    match (earliest, latest) {
+        (result @ Single(_), _) => result,
+        (_, result @ Single(_)) => result,
+        (Ambiguous(offset, _), _) => Single(offset),
+        (_, Ambiguous(offset, _)) => Single(offset),
+        (None, None) => None,
     }
 }
 
@@ -339,45 +578,45 @@ impl GapInfo {
     pub fn new(local: &NaiveDateTime, tz: &Tz) -> Option<Self> {
         let timestamp = local.and_utc().timestamp();
         let timespans = tz.timespans();
-        let index = binary_search(0, timespans.len(), |i| {
-            timespans.local_span(i).cmp(timestamp)
-        });
-
-        let Err(end_idx) = index else {
-            return None;
-        };
+        if let Some(footer) = timespans.footer_at_local(timestamp) {
+            let (start_time, before) = footer.gap_at(timestamp)?;
+            let start_time = DateTime::from_timestamp(start_time, 0);
+            return Some(Self {
+                begin: start_time.map(|start_time| {
+                    let local = start_time.naive_utc() + Duration::seconds(before.offset() as i64);
+                    (local, TzOffset::new(*tz, before))
+                }),
+                end: start_time.map(|start_time| tz.from_utc_datetime(&start_time.naive_utc())),
+            });
+        }
 
-        let begin = match end_idx {
-            0 => None,
-            _ => {
-                let start_idx = end_idx - 1;
-
-                timespans
-                    .local_span(start_idx)
-                    .end
-                    .and_then(|start_time| DateTime::from_timestamp(start_time, 0))
-                    .map(|start_time| {
-                        (
-                            start_time.naive_local(),
-                            TzOffset::new(*tz, timespans.get(start_idx)),
-                        )
-                    })
+        // The gap is between the last span that ends before it and the first that begins after.
+        let mut before = None;
+        let mut after = None;
+        for (span, index) in timespans.spans_from_local(timestamp) {
+            let timespan = timespans.timespan(index);
+            let span = span.local(timespan);
+            if span.contains(timestamp) {
+                return None;
             }
-        };
-
-        let end = match end_idx {
-            _ if end_idx >= timespans.len() => None,
-            _ => {
-                timespans
-                    .local_span(end_idx)
-                    .begin
-                    .and_then(|end_time| DateTime::from_timestamp(end_time, 0))
-                    .and_then(|date_time| {
-                        // we create the DateTime from a timestamp that exists in the timezone
-                        tz.from_local_datetime(&date_time.naive_local()).single()
-                    })
+            if let Some(begin) = span.begin.filter(|&begin| begin > timestamp) {
+                after = Some(begin);
+                break;
             }
-        };
+            before = Some((span.end, timespan));
+        }
+
+        let begin = before.and_then(|(end, timespan)| {
+            end.and_then(|start_time| DateTime::from_timestamp(start_time, 0))
+                .map(|start_time| (start_time.naive_local(), TzOffset::new(*tz, timespan)))
+        });
+
+        let end = after
+            .and_then(|end_time| DateTime::from_timestamp(end_time, 0))
+            .and_then(|date_time| {
+                // we create the DateTime from a timestamp that exists in the timezone
+                tz.from_local_datetime(&date_time.naive_local()).single()
+            });
 
         Some(Self { begin, end })
     }
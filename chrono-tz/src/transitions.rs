@@ -0,0 +1,201 @@
+use core::fmt::{self, Debug, Formatter};
+use core::iter::FusedIterator;
+use core::ops::{Bound, RangeBounds};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+use crate::timezone_impl::{FixedTimespanSet, TimeSpans, TzOffset};
+use crate::timezones::Tz;
+
+/// A change from one offset to another in a timezone
+///
+/// ```
+/// # extern crate chrono;
+/// # extern crate chrono_tz;
+/// use chrono::{TimeZone, Utc};
+/// use chrono_tz::Europe::London;
+///
+/// let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+/// let transition = London.transitions_in(start..).next().unwrap();
+///
+/// assert_eq!(transition.at, Utc.with_ymd_and_hms(2024, 3, 31, 1, 0, 0).unwrap());
+/// assert_eq!(transition.before.to_string(), "GMT");
+/// assert_eq!(transition.after.to_string(), "BST");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Transition {
+    /// The instant at which `after` takes effect
+    pub at: DateTime<Utc>,
+    /// The offset in effect up to the transition
+    pub before: TzOffset,
+    /// The offset in effect from the transition on
+    pub after: TzOffset,
+}
+
+/// An iterator over the transitions of a [`Tz`], in chronological order
+///
+/// This is created by [`Tz::transitions`] and [`Tz::transitions_in`].
+#[derive(Clone)]
+pub struct Transitions {
+    tz: Tz,
+    timespans: FixedTimespanSet,
+    front: usize,
+    back: usize,
+}
+
+impl Transition {
+    fn new(tz: Tz, timespans: &FixedTimespanSet, index: usize) -> Option<Self> {
+        let (at, before, after) = timespans.transition(index)?;
+        Some(Transition {
+            at: DateTime::from_timestamp(at, 0)?,
+            before: TzOffset::new(tz, before),
+            after: TzOffset::new(tz, after),
+        })
+    }
+}
+
+impl Transitions {
+    fn transition(&self, index: usize) -> Option<Transition> {
+        Transition::new(self.tz, &self.timespans, index)
+    }
+}
+
+impl Iterator for Transitions {
+    type Item = Transition;
+
+    fn next(&mut self) -> Option<Transition> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.front += 1;
+        self.transition(self.front - 1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back.saturating_sub(self.front);
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Transition> {
+        self.front = self.front.saturating_add(n).min(self.back);
+        self.next()
+    }
+}
+
+impl DoubleEndedIterator for Transitions {
+    fn next_back(&mut self) -> Option<Transition> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        self.transition(self.back)
+    }
+}
+
+impl ExactSizeIterator for Transitions {}
+
+impl FusedIterator for Transitions {}
+
+impl Debug for Transitions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Transitions")
+            .field("tz", &self.tz)
+            .field("remaining", &self.len())
+            .finish()
+    }
+}
+
+impl Tz {
+    /// Returns an iterator over all the transitions of this timezone
+    ///
+    /// Zones that still observe daylight saving time keep changing offsets after the last
+    /// transition in the tz database; for those the iterator goes on with the transitions their
+    /// current rules produce, up to the last instant chrono can represent. Use
+    /// [`Tz::transitions_in`] to only look at a specific period.
+    pub fn transitions(&self) -> Transitions {
+        self.transitions_in(..)
+    }
+
+    /// Returns an iterator over the transitions of this timezone that happen within `range`
+    ///
+    /// ```
+    /// # extern crate chrono;
+    /// # extern crate chrono_tz;
+    /// use chrono::{TimeZone, Utc};
+    /// use chrono_tz::America::New_York;
+    ///
+    /// let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    /// let end = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+    /// let names = New_York
+    ///     .transitions_in(start..end)
+    ///     .map(|transition| transition.after.to_string())
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(names, ["EDT", "EST"]);
+    /// ```
+    pub fn transitions_in<R: RangeBounds<DateTime<Utc>>>(&self, range: R) -> Transitions {
+        let timespans = self.timespans();
+        let front = match range.start_bound() {
+            Bound::Included(start) => transitions_before(&timespans, start),
+            Bound::Excluded(start) => timespans.transitions_until(start.timestamp()),
+            Bound::Unbounded => 0,
+        };
+        let back = match range.end_bound() {
+            Bound::Included(end) => timespans.transitions_until(end.timestamp()),
+            Bound::Excluded(end) => transitions_before(&timespans, end),
+            Bound::Unbounded => {
+                timespans.transitions_until(NaiveDateTime::MAX.and_utc().timestamp())
+            }
+        };
+
+        // The count comes from the rules in the footer, which can put the last of them past
+        // what chrono can represent, so the iterator couldn't return those.
+        let mut back = back.max(front);
+        while back > front && Transition::new(*self, &timespans, back - 1).is_none() {
+            back -= 1;
+        }
+
+        Transitions {
+            tz: *self,
+            timespans,
+            front,
+            back,
+        }
+    }
+
+    /// Returns the first transition of this timezone that happens after `instant`
+    ///
+    /// ```
+    /// # extern crate chrono;
+    /// # extern crate chrono_tz;
+    /// use chrono::{TimeZone, Utc};
+    /// use chrono_tz::Europe::Berlin;
+    ///
+    /// let now = Utc.with_ymd_and_hms(2024, 3, 28, 12, 0, 0).unwrap();
+    /// let transition = Berlin.next_transition(&now).unwrap();
+    ///
+    /// assert_eq!(transition.at, Utc.with_ymd_and_hms(2024, 3, 31, 1, 0, 0).unwrap());
+    /// assert_eq!(transition.after.to_string(), "CEST");
+    /// ```
+    pub fn next_transition(&self, instant: &DateTime<Utc>) -> Option<Transition> {
+        let timespans = self.timespans();
+        let index = timespans.transitions_until(instant.timestamp());
+        Transition::new(*self, &timespans, index)
+    }
+
+    /// Returns the last transition of this timezone that happens before `instant`
+    pub fn previous_transition(&self, instant: &DateTime<Utc>) -> Option<Transition> {
+        let timespans = self.timespans();
+        let index = transitions_before(&timespans, instant).checked_sub(1)?;
+        Transition::new(*self, &timespans, index)
+    }
+}
+
+/// The number of transitions that happen strictly before the given instant
+fn transitions_before(timespans: &FixedTimespanSet, instant: &DateTime<Utc>) -> usize {
+    // Transitions always happen on a whole second.
+    match instant.timestamp_subsec_nanos() {
+        0 => timespans.transitions_until(instant.timestamp() - 1),
+        _ => timespans.transitions_until(instant.timestamp()),
+    }
+}
@@ -0,0 +1,824 @@
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Display, Formatter};
+use std::error::Error;
+use std::iter;
+use std::path::Path;
+use std::sync::Arc;
+use std::{fs, io};
+
+use chrono::{
+    DateTime, Duration, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeZone,
+};
+
+use crate::binary_search::binary_search;
+use crate::posix::{PosixTz, Timespan};
+use crate::timezone_impl::{offset_from_local_date, OffsetComponents, OffsetName};
+
+/// The directory [`TzFile::named`] looks up zones in
+const ZONEINFO_DIR: &str = "/usr/share/zoneinfo";
+
+/// A timezone loaded at runtime from a compiled TZif file
+///
+/// Unlike [`Tz`](crate::Tz), whose data is fixed when chrono-tz is built, this reads the
+/// `zoneinfo` files that `zic` produces, so it follows the tz database installed on the system.
+/// Versions 1 to 4 of the format ([RFC 8536]) are supported, including the POSIX `TZ` string
+/// footer that describes the offsets after the last transition.
+///
+/// Cloning a `TzFile` is cheap, as the parsed data is shared.
+///
+/// ```no_run
+/// # extern crate chrono;
+/// # extern crate chrono_tz;
+/// use chrono::TimeZone;
+/// use chrono_tz::{OffsetName, TzFile};
+///
+/// let tz = TzFile::named("Europe/Paris").unwrap();
+/// let dt = tz.with_ymd_and_hms(2024, 7, 1, 12, 0, 0).unwrap();
+/// assert_eq!(dt.offset().abbreviation(), "CEST");
+/// ```
+///
+/// [RFC 8536]: https://datatracker.ietf.org/doc/html/rfc8536
+#[derive(Clone)]
+pub struct TzFile {
+    data: Arc<TzFileData>,
+}
+
+#[derive(PartialEq, Eq)]
+struct TzFileData {
+    name: Option<String>,
+    /// The instants at which the local time type changes, with the index of the new type
+    transitions: Vec<(i64, usize)>,
+    /// The local time types; the first one is in effect before the first transition
+    types: Vec<LocalTimeType>,
+    /// NUL-terminated abbreviations that the local time types point into
+    abbreviations: String,
+    footer: Option<PosixTz<LocalTimeType>>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct LocalTimeType {
+    utc_offset: i32,
+    is_dst: bool,
+    /// The part of `utc_offset` that is daylight saving time
+    dst_offset: i32,
+    /// The start of the abbreviation in `TzFileData::abbreviations`
+    abbreviation: usize,
+}
+
+impl Timespan for LocalTimeType {
+    fn total_offset(&self) -> i32 {
+        self.utc_offset
+    }
+}
+
+impl TzFile {
+    /// Parses the contents of a TZif file
+    pub fn parse(bytes: &[u8]) -> Result<Self, TzFileError> {
+        Ok(TzFile {
+            data: Arc::new(TzFileData::parse(bytes, None)?),
+        })
+    }
+
+    /// Loads the zone with the given name, such as `America/New_York`, from the system's
+    /// `/usr/share/zoneinfo` directory
+    pub fn named(name: &str) -> Result<Self, TzFileError> {
+        let path = Path::new(name);
+        let is_relative = path
+            .components()
+            .all(|c| matches!(c, std::path::Component::Normal(_)));
+        if name.is_empty() || !is_relative {
+            return Err(TzFileError::InvalidName);
+        }
+
+        let bytes = fs::read(Path::new(ZONEINFO_DIR).join(path)).map_err(TzFileError::Io)?;
+        Ok(TzFile {
+            data: Arc::new(TzFileData::parse(&bytes, Some(name.to_owned()))?),
+        })
+    }
+
+    /// The name this zone was loaded with, if it was loaded with [`TzFile::named`]
+    pub fn name(&self) -> Option<&str> {
+        self.data.name.as_deref()
+    }
+
+    fn offset(&self, local_time_type: LocalTimeType) -> TzFileOffset {
+        TzFileOffset {
+            tz: self.clone(),
+            local_time_type,
+        }
+    }
+
+    /// The local time type in effect at the given Unix timestamp
+    fn type_at(&self, timestamp: i64) -> LocalTimeType {
+        let data = &*self.data;
+        let index = data.transitions.partition_point(|&(at, _)| at <= timestamp);
+        match (index, &data.footer) {
+            (i, Some(footer)) if i == data.transitions.len() => footer.timespan_at(timestamp),
+            (0, _) => data.types[0],
+            (i, _) => data.types[data.transitions[i - 1].1],
+        }
+    }
+
+    /// The local time types in effect before and after each transition, in order
+    fn transition(&self, index: usize) -> (i64, LocalTimeType, LocalTimeType) {
+        let data = &*self.data;
+        let before = match index {
+            0 => data.types[0],
+            _ => data.types[data.transitions[index - 1].1],
+        };
+        let (at, after) = data.transitions[index];
+        (at, before, data.types[after])
+    }
+}
+
+impl TzFileData {
+    fn parse(bytes: &[u8], name: Option<String>) -> Result<Self, TzFileError> {
+        let mut reader = Reader { bytes };
+        let header = reader.header()?;
+        let (header, time_size) = match header.version {
+            // Version 1 files only have the block with 32-bit times.
+            0 => (header, 4),
+            _ => {
+                reader.skip(header.data_len(4).ok_or(TzFileError::UnexpectedEof)?)?;
+                (reader.header()?, 8)
+            }
+        };
+        // Check the counts against the data before allocating anything for them.
+        match header.data_len(time_size) {
+            Some(len) if len <= reader.bytes.len() => {}
+            _ => return Err(TzFileError::UnexpectedEof),
+        }
+
+        let mut transitions = Vec::with_capacity(header.time_count);
+        for _ in 0..header.time_count {
+            transitions.push((reader.time(time_size)?, 0));
+        }
+        for transition in &mut transitions {
+            transition.1 = reader.take(1)?[0] as usize;
+        }
+
+        let mut types = Vec::with_capacity(header.type_count);
+        for _ in 0..header.type_count {
+            let utc_offset = reader.time(4)? as i32;
+            let is_dst = match reader.take(1)?[0] {
+                0 => false,
+                1 => true,
+                _ => return Err(TzFileError::InvalidData("DST flag is not 0 or 1")),
+            };
+            let abbreviation = reader.take(1)?[0] as usize;
+            types.push(LocalTimeType {
+                utc_offset,
+                is_dst,
+                dst_offset: 0,
+                abbreviation,
+            });
+        }
+
+        let abbreviations = String::from_utf8(reader.take(header.char_count)?.to_vec())
+            .map_err(|_| TzFileError::InvalidData("abbreviations are not valid UTF-8"))?;
+
+        // Leap second records and the standard/wall and UT/local indicators aren't needed to
+        // compute offsets.
+        reader.skip(header.leap_count * (time_size + 4) + header.std_count + header.ut_count)?;
+
+        if types.is_empty() || abbreviations.is_empty() {
+            return Err(TzFileError::InvalidData("no local time types"));
+        }
+        // RFC 8536 allows offsets of up to 25 hours, which chrono can't represent.
+        if types
+            .iter()
+            .any(|t| FixedOffset::east_opt(t.utc_offset).is_none())
+        {
+            return Err(TzFileError::InvalidData("UT offset out of range"));
+        }
+        if transitions.iter().any(|&(_, index)| index >= types.len()) {
+            return Err(TzFileError::InvalidData(
+                "transition to an unknown local time type",
+            ));
+        }
+        if transitions.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+            return Err(TzFileError::InvalidData(
+                "transitions are not in ascending order",
+            ));
+        }
+        let terminated = |start: usize| {
+            abbreviations
+                .get(start..)
+                .map_or(false, |s| s.contains('\0'))
+        };
+        if !types.iter().all(|t| terminated(t.abbreviation)) {
+            return Err(TzFileError::InvalidData("abbreviation out of bounds"));
+        }
+
+        // The file only flags the types that are daylight saving time, so how much time they
+        // save is told by the standard time right before or after them.
+        let sequence = iter::once(0)
+            .chain(transitions.iter().map(|&(_, index)| index))
+            .collect::<Vec<_>>();
+        let dst_offsets = (0..types.len())
+            .map(|index| {
+                let utc_offset = types[index].utc_offset;
+                let standard = |i: usize| Some(types[*sequence.get(i)?]).filter(|t| !t.is_dst);
+                (0..sequence.len())
+                    .filter(|&i| sequence[i] == index)
+                    .find_map(|i| {
+                        i.checked_sub(1)
+                            .and_then(standard)
+                            .or_else(|| standard(i + 1))
+                    })
+                    .map_or(0, |standard| utc_offset - standard.utc_offset)
+            })
+            .collect::<Vec<_>>();
+        for (local_time_type, dst_offset) in types.iter_mut().zip(dst_offsets) {
+            if local_time_type.is_dst {
+                local_time_type.dst_offset = dst_offset;
+            }
+        }
+
+        let mut data = TzFileData {
+            name,
+            transitions,
+            types,
+            abbreviations,
+            footer: None,
+        };
+        if header.version != 0 {
+            data.footer = data.parse_footer(reader.bytes)?;
+        }
+        Ok(data)
+    }
+
+    /// Parses the newline-enclosed POSIX `TZ` string at the end of version 2+ files
+    fn parse_footer(
+        &mut self,
+        footer: &[u8],
+    ) -> Result<Option<PosixTz<LocalTimeType>>, TzFileError> {
+        let footer = footer
+            .strip_prefix(b"\n")
+            .and_then(|footer| footer.strip_suffix(b"\n"))
+            .and_then(|footer| core::str::from_utf8(footer).ok())
+            .ok_or(TzFileError::InvalidFooter)?;
+        // An empty footer means there is no rule for instants after the last transition.
+        if footer.is_empty() {
+            return Ok(None);
+        }
+
+        let footer = PosixTz::parse(footer).ok_or(TzFileError::InvalidFooter)?;
+        let offsets = iter::once(&footer.std).chain(footer.dst.as_ref().map(|dst| &dst.timespan));
+        if offsets
+            .map(|timespan| timespan.offset)
+            .any(|offset| FixedOffset::east_opt(offset).is_none())
+        {
+            return Err(TzFileError::InvalidFooter);
+        }
+        let std_offset = footer.std.offset;
+        Ok(Some(footer.map(|timespan, is_dst| LocalTimeType {
+            utc_offset: timespan.offset,
+            is_dst,
+            dst_offset: match is_dst {
+                true => timespan.offset - std_offset,
+                false => 0,
+            },
+            abbreviation: self.intern_abbreviation(timespan.name),
+        })))
+    }
+
+    /// Returns where the given abbreviation starts, adding it if it's not there yet
+    fn intern_abbreviation(&mut self, name: &str) -> usize {
+        let mut start = 0;
+        for abbreviation in self.abbreviations.split('\0') {
+            if abbreviation == name {
+                return start;
+            }
+            start += abbreviation.len() + 1;
+        }
+
+        let start = self.abbreviations.len();
+        self.abbreviations.push_str(name);
+        self.abbreviations.push('\0');
+        start
+    }
+
+    fn abbreviation(&self, local_time_type: &LocalTimeType) -> &str {
+        let abbreviation = &self.abbreviations[local_time_type.abbreviation..];
+        abbreviation.split('\0').next().unwrap_or_default()
+    }
+
+    /// The largest total offset from UTC this zone ever has
+    fn max_offset(&self) -> i32 {
+        let types = self.types.iter().map(|t| t.utc_offset);
+        let footer = self.footer.as_ref().map(|footer| footer.max_offset());
+        types.chain(footer).max().unwrap_or_default()
+    }
+}
+
+struct Header {
+    version: u8,
+    ut_count: usize,
+    std_count: usize,
+    leap_count: usize,
+    time_count: usize,
+    type_count: usize,
+    char_count: usize,
+}
+
+impl Header {
+    /// The length of the data block that follows this header, given the size of its times, or
+    /// `None` if it overflows
+    fn data_len(&self, time_size: usize) -> Option<usize> {
+        [
+            self.time_count.checked_mul(time_size + 1)?,
+            self.type_count.checked_mul(6)?,
+            self.char_count,
+            self.leap_count.checked_mul(time_size + 4)?,
+            self.std_count,
+            self.ut_count,
+        ]
+        .into_iter()
+        .try_fold(0usize, |len, part| len.checked_add(part))
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], TzFileError> {
+        if self.bytes.len() < len {
+            return Err(TzFileError::UnexpectedEof);
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn skip(&mut self, len: usize) -> Result<(), TzFileError> {
+        self.take(len).map(|_| ())
+    }
+
+    /// A big-endian signed integer of 4 or 8 bytes
+    fn time(&mut self, size: usize) -> Result<i64, TzFileError> {
+        let bytes = self.take(size)?;
+        Ok(match size {
+            4 => i32::from_be_bytes(bytes.try_into().unwrap()) as i64,
+            _ => i64::from_be_bytes(bytes.try_into().unwrap()),
+        })
+    }
+
+    fn count(&mut self) -> Result<usize, TzFileError> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()) as usize)
+    }
+
+    fn header(&mut self) -> Result<Header, TzFileError> {
+        if self.take(4)? != b"TZif" {
+            return Err(TzFileError::InvalidMagic);
+        }
+        let version = match self.take(1)?[0] {
+            0 => 0,
+            version @ b'2'..=b'4' => version - b'0',
+            version => return Err(TzFileError::UnsupportedVersion(version)),
+        };
+        self.skip(15)?;
+
+        Ok(Header {
+            version,
+            ut_count: self.count()?,
+            std_count: self.count()?,
+            leap_count: self.count()?,
+            time_count: self.count()?,
+            type_count: self.count()?,
+            char_count: self.count()?,
+        })
+    }
+}
+
+impl TimeZone for TzFile {
+    type Offset = TzFileOffset;
+
+    fn from_offset(offset: &Self::Offset) -> Self {
+        offset.tz.clone()
+    }
+
+    fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<Self::Offset> {
+        offset_from_local_date(self, local)
+    }
+
+    // A local time exists with a given offset if converting it to UTC with that offset and
+    // back lands on the same offset. Zones only ever use a handful of offsets, so trying each
+    // of them is cheap.
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<Self::Offset> {
+        let timestamp = local.and_utc().timestamp();
+        let mut earliest: Option<(i64, LocalTimeType)> = None;
+        let mut latest: Option<(i64, LocalTimeType)> = None;
+        let candidates = self.data.types.iter().copied();
+        let footer = self
+            .data
+            .footer
+            .iter()
+            .flat_map(|f| [Some(f.std), f.dst.map(|d| d.timespan)]);
+        for candidate in candidates.chain(footer.flatten()) {
+            let utc = timestamp - candidate.utc_offset as i64;
+            let found = self.type_at(utc);
+            if found.utc_offset != candidate.utc_offset {
+                continue;
+            }
+            if earliest.map_or(true, |(at, _)| utc < at) {
+                earliest = Some((utc, found));
+            }
+            if latest.map_or(true, |(at, _)| utc > at) {
+                latest = Some((utc, found));
+            }
+        }
+
+        match (earliest, latest) {
+            (Some((a, first)), Some((b, last))) if a != b => {
+                LocalResult::Ambiguous(self.offset(first), self.offset(last))
+            }
+            (Some((_, found)), _) => LocalResult::Single(self.offset(found)),
+            _ => LocalResult::None,
+        }
+    }
+
+    #[allow(deprecated)]
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> Self::Offset {
+        self.offset_from_utc_datetime(&utc.and_time(NaiveTime::MIN))
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> Self::Offset {
+        self.offset(self.type_at(utc.and_utc().timestamp()))
+    }
+}
+
+impl PartialEq for TzFile {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.data, &other.data) || self.data == other.data
+    }
+}
+
+impl Eq for TzFile {}
+
+impl Debug for TzFile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => f.write_str("TzFile"),
+        }
+    }
+}
+
+/// The offset of a [`TzFile`] at some instant
+#[derive(Clone, PartialEq, Eq)]
+pub struct TzFileOffset {
+    tz: TzFile,
+    local_time_type: LocalTimeType,
+}
+
+impl Offset for TzFileOffset {
+    fn fix(&self) -> FixedOffset {
+        FixedOffset::east_opt(self.local_time_type.utc_offset).unwrap()
+    }
+}
+
+impl OffsetName for TzFileOffset {
+    fn tz_id(&self) -> &str {
+        self.tz.name().unwrap_or_default()
+    }
+
+    fn abbreviation(&self) -> &str {
+        self.tz.data.abbreviation(&self.local_time_type)
+    }
+}
+
+impl OffsetComponents for TzFileOffset {
+    fn base_utc_offset(&self) -> Duration {
+        let local_time_type = &self.local_time_type;
+        Duration::seconds((local_time_type.utc_offset - local_time_type.dst_offset) as i64)
+    }
+
+    fn dst_offset(&self) -> Duration {
+        Duration::seconds(self.local_time_type.dst_offset as i64)
+    }
+
+    fn is_dst(&self) -> bool {
+        self.local_time_type.is_dst
+    }
+}
+
+impl Display for TzFileOffset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.abbreviation())
+    }
+}
+
+impl Debug for TzFileOffset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+/// Represents the information of a gap in a [`TzFile`]
+///
+/// This is the counterpart of [`GapInfo`](crate::GapInfo) for zones loaded at runtime.
+pub struct TzFileGapInfo {
+    /// When available it contains information about the beginning of the gap.
+    ///
+    /// The time represents the first instant in which the gap starts, and the offset is the
+    /// one in effect right before it.
+    pub begin: Option<(NaiveDateTime, TzFileOffset)>,
+    /// When available it contains the first instant after the gap.
+    pub end: Option<DateTime<TzFile>>,
+}
+
+impl TzFileGapInfo {
+    /// Return information about a gap.
+    ///
+    /// It returns `None` if `local` is not in a gap for the given timezone.
+    pub fn new(local: &NaiveDateTime, tz: &TzFile) -> Option<Self> {
+        let timestamp = local.and_utc().timestamp();
+        let data = &*tz.data;
+        let last = data.transitions.last().map(|&(at, _)| at);
+        let (start, before) = match &data.footer {
+            Some(footer)
+                if last.map_or(true, |last| timestamp - data.max_offset() as i64 >= last) =>
+            {
+                footer.gap_at(timestamp)?
+            }
+            _ => {
+                // Find the last transition that starts, in local time, at or before `local`.
+                let index = binary_search(0, data.transitions.len(), |i| {
+                    let (at, before, _) = tz.transition(i);
+                    match at + before.utc_offset as i64 <= timestamp {
+                        true => Ordering::Less,
+                        false => Ordering::Greater,
+                    }
+                })
+                .unwrap_or_else(|index| index);
+                let (at, before, after) = tz.transition(index.checked_sub(1)?);
+                if timestamp >= at + after.utc_offset as i64 {
+                    return None;
+                }
+                (at, before)
+            }
+        };
+
+        let start = DateTime::from_timestamp(start, 0);
+        Some(TzFileGapInfo {
+            begin: start.map(|start| {
+                let local = start.naive_utc() + Duration::seconds(before.utc_offset as i64);
+                (local, tz.offset(before))
+            }),
+            end: start.map(|start| tz.from_utc_datetime(&start.naive_utc())),
+        })
+    }
+}
+
+/// An error that occurred while loading a [`TzFile`]
+#[derive(Debug)]
+pub enum TzFileError {
+    /// The zone file couldn't be read
+    Io(io::Error),
+    /// The zone name is empty or isn't a relative path within the zoneinfo directory
+    InvalidName,
+    /// The data doesn't start with the `TZif` magic bytes
+    InvalidMagic,
+    /// The version of the format isn't one of the supported versions 1 to 4
+    UnsupportedVersion(u8),
+    /// The data ends before all the sections announced in its header
+    UnexpectedEof,
+    /// The data is inconsistent
+    InvalidData(&'static str),
+    /// The footer isn't a valid POSIX `TZ` string
+    InvalidFooter,
+}
+
+impl Display for TzFileError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TzFileError::Io(err) => write!(f, "failed to read zone file: {err}"),
+            TzFileError::InvalidName => f.write_str("invalid zone name"),
+            TzFileError::InvalidMagic => f.write_str("not a TZif file"),
+            TzFileError::UnsupportedVersion(version) => {
+                write!(f, "unsupported TZif version {version:#04x}")
+            }
+            TzFileError::UnexpectedEof => f.write_str("unexpected end of TZif data"),
+            TzFileError::InvalidData(reason) => write!(f, "invalid TZif data: {reason}"),
+            TzFileError::InvalidFooter => f.write_str("invalid TZif footer"),
+        }
+    }
+}
+
+impl Error for TzFileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TzFileError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TzFile, TzFileError, TzFileGapInfo};
+    use crate::{OffsetComponents, OffsetName};
+    use chrono::{Duration, LocalResult, NaiveDate, Offset, TimeZone, Utc};
+
+    /// Builds a version 2 file with the given transitions, types and footer
+    fn tzif(transitions: &[(i64, u8)], types: &[(i32, bool, &str)], footer: &str) -> Vec<u8> {
+        let mut abbreviations = Vec::new();
+        let mut type_records = Vec::new();
+        for &(offset, is_dst, name) in types {
+            type_records.extend_from_slice(&offset.to_be_bytes());
+            type_records.push(is_dst as u8);
+            type_records.push(abbreviations.len() as u8);
+            abbreviations.extend_from_slice(name.as_bytes());
+            abbreviations.push(0);
+        }
+
+        let header = |counts: [usize; 6]| {
+            let mut header = b"TZif2".to_vec();
+            header.extend_from_slice(&[0; 15]);
+            for count in counts {
+                header.extend_from_slice(&(count as u32).to_be_bytes());
+            }
+            header
+        };
+        let counts = [0, 0, 0, transitions.len(), types.len(), abbreviations.len()];
+
+        let mut bytes = header(counts);
+        for &(at, _) in transitions {
+            bytes.extend_from_slice(&(at as i32).to_be_bytes());
+        }
+        bytes.extend(transitions.iter().map(|&(_, index)| index));
+        bytes.extend_from_slice(&type_records);
+        bytes.extend_from_slice(&abbreviations);
+
+        bytes.extend(header(counts));
+        for &(at, _) in transitions {
+            bytes.extend_from_slice(&at.to_be_bytes());
+        }
+        bytes.extend(transitions.iter().map(|&(_, index)| index));
+        bytes.extend_from_slice(&type_records);
+        bytes.extend_from_slice(&abbreviations);
+        bytes.extend(format!("\n{footer}\n").bytes());
+        bytes
+    }
+
+    fn new_york() -> TzFile {
+        // The 2007 transitions of America/New_York, followed by its current rules
+        let bytes = tzif(
+            &[(1173596400, 1), (1194156000, 0)],
+            &[(-18000, false, "EST"), (-14400, true, "EDT")],
+            "EST5EDT,M3.2.0,M11.1.0",
+        );
+        TzFile::parse(&bytes).unwrap()
+    }
+
+    #[test]
+    fn utc_offsets() {
+        let tz = new_york();
+        for (year, month, abbreviation, offset) in [
+            (2000, 7, "EST", -5),
+            (2007, 1, "EST", -5),
+            (2007, 7, "EDT", -4),
+            (2007, 12, "EST", -5),
+            (2150, 7, "EDT", -4),
+            (2150, 12, "EST", -5),
+        ] {
+            let dt = Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).unwrap();
+            let offset_at = tz.offset_from_utc_datetime(&dt.naive_utc());
+            assert_eq!(offset_at.abbreviation(), abbreviation);
+            assert_eq!(offset_at.fix().local_minus_utc(), offset * 3600);
+        }
+    }
+
+    #[test]
+    fn local_offsets() {
+        let tz = new_york();
+        let local = |y, m, d, h| {
+            NaiveDate::from_ymd_opt(y, m, d)
+                .unwrap()
+                .and_hms_opt(h, 30, 0)
+                .unwrap()
+        };
+
+        for year in [2007, 2150] {
+            let (spring, autumn) = match year {
+                2007 => (11, 4),
+                _ => (8, 1),
+            };
+            assert_eq!(
+                tz.from_local_datetime(&local(year, 3, spring, 2)),
+                LocalResult::None
+            );
+            let LocalResult::Ambiguous(earliest, latest) =
+                tz.from_local_datetime(&local(year, 11, autumn, 1))
+            else {
+                panic!("expected an ambiguous time in {year}");
+            };
+            assert_eq!(earliest.offset().abbreviation(), "EDT");
+            assert_eq!(latest.offset().abbreviation(), "EST");
+
+            let gap = TzFileGapInfo::new(&local(year, 3, spring, 2), &tz).unwrap();
+            let (begin, offset) = gap.begin.unwrap();
+            assert_eq!(
+                begin,
+                local(year, 3, spring, 2) - chrono::Duration::minutes(30)
+            );
+            assert_eq!(offset.abbreviation(), "EST");
+            assert_eq!(
+                gap.end.unwrap().naive_local(),
+                local(year, 3, spring, 3) - chrono::Duration::minutes(30)
+            );
+        }
+        assert!(TzFileGapInfo::new(&local(2007, 3, 11, 3), &tz).is_none());
+    }
+
+    #[test]
+    fn offset_components() {
+        let tz = new_york();
+        for year in [2007, 2150] {
+            let winter = tz.with_ymd_and_hms(year, 1, 1, 0, 0, 0).unwrap();
+            assert_eq!(winter.offset().base_utc_offset(), Duration::hours(-5));
+            assert_eq!(winter.offset().dst_offset(), Duration::zero());
+            assert!(!winter.offset().is_dst());
+
+            let summer = tz.with_ymd_and_hms(year, 7, 1, 0, 0, 0).unwrap();
+            assert_eq!(summer.offset().base_utc_offset(), Duration::hours(-5));
+            assert_eq!(summer.offset().dst_offset(), Duration::hours(1));
+            assert!(summer.offset().is_dst());
+        }
+    }
+
+    #[test]
+    fn footer_only() {
+        let bytes = tzif(&[], &[(19800, false, "IST")], "IST-5:30");
+        let tz = TzFile::parse(&bytes).unwrap();
+        let dt = tz.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(dt.offset().fix().local_minus_utc(), 19800);
+        assert_eq!(dt.offset().to_string(), "IST");
+        assert_eq!(dt.offset().tz_id(), "");
+    }
+
+    #[test]
+    fn invalid() {
+        let bytes = tzif(&[(0, 2)], &[(0, false, "UTC")], "UTC0");
+        assert!(matches!(
+            TzFile::parse(&bytes),
+            Err(TzFileError::InvalidData(_))
+        ));
+        assert!(matches!(
+            TzFile::parse(b"TZjf"),
+            Err(TzFileError::InvalidMagic)
+        ));
+
+        let bytes = tzif(&[], &[(0, false, "UTC")], "UTC0");
+        assert!(matches!(
+            TzFile::parse(&bytes[..bytes.len() - 10]),
+            Err(TzFileError::InvalidFooter | TzFileError::UnexpectedEof)
+        ));
+        assert!(matches!(
+            TzFile::parse(&bytes[..60]),
+            Err(TzFileError::UnexpectedEof)
+        ));
+
+        // Offsets of a day or more are valid TZif, but not something chrono can represent.
+        let bytes = tzif(&[], &[(90000, false, "XXX")], "");
+        assert!(matches!(
+            TzFile::parse(&bytes),
+            Err(TzFileError::InvalidData(_))
+        ));
+        let bytes = tzif(&[], &[(0, false, "UTC")], "AAA24");
+        assert!(matches!(
+            TzFile::parse(&bytes),
+            Err(TzFileError::InvalidFooter)
+        ));
+
+        // Counts that the data can't hold are an error rather than a huge allocation.
+        let mut bytes = b"TZif2".to_vec();
+        bytes.extend_from_slice(&[0; 15]);
+        for count in [0, 0, 0, u32::MAX, 1, 4] {
+            bytes.extend_from_slice(&count.to_be_bytes());
+        }
+        bytes.extend_from_slice(&[0; 64]);
+        assert!(matches!(
+            TzFile::parse(&bytes),
+            Err(TzFileError::UnexpectedEof)
+        ));
+        bytes[5] = 0;
+        assert!(matches!(
+            TzFile::parse(&bytes),
+            Err(TzFileError::UnexpectedEof)
+        ));
+
+        assert!(matches!(
+            TzFile::named("../etc/passwd"),
+            Err(TzFileError::InvalidName)
+        ));
+        assert!(matches!(
+            TzFile::named("/etc/localtime"),
+            Err(TzFileError::InvalidName)
+        ));
+    }
+}
@@ -0,0 +1,51 @@
+use crate::timezones::{Tz, WINDOWS_IDS, WINDOWS_ZONES};
+
+impl Tz {
+    /// Returns the timezone a Windows time zone ID stands for
+    ///
+    /// Windows IDs such as `Pacific Standard Time` are what Windows itself, .NET and Outlook use
+    /// to name timezones. One ID often covers several regions, so the [CLDR mapping] this is based
+    /// on picks a zone per territory: pass the ISO 3166 country code of the user to get the zone
+    /// of their country, or `None` to get the zone CLDR considers the main one. An unknown
+    /// territory falls back to the main zone as well.
+    ///
+    /// CLDR keeps names some zones had in the past, so this may return a link such as
+    /// `Asia/Calcutta` rather than `Asia/Kolkata`, unless the `exclude-backward-links` feature
+    /// leaves such links out.
+    ///
+    /// ```
+    /// use chrono_tz::Tz;
+    ///
+    /// let tz = Tz::from_windows_id("Pacific Standard Time", None);
+    /// assert_eq!(tz, Some(Tz::America__Los_Angeles));
+    ///
+    /// let tz = Tz::from_windows_id("Pacific Standard Time", Some("CA"));
+    /// assert_eq!(tz, Some(Tz::America__Vancouver));
+    /// ```
+    ///
+    /// [CLDR mapping]: https://github.com/unicode-org/cldr/blob/main/common/supplemental/windowsZones.xml
+    pub fn from_windows_id(id: &str, territory: Option<&str>) -> Option<Tz> {
+        let zones = WINDOWS_ZONES.get(id)?;
+        let zone = |territory: &str| {
+            zones
+                .iter()
+                .find(|(t, _)| t.eq_ignore_ascii_case(territory))
+                .map(|(_, tz)| *tz)
+        };
+        territory.and_then(zone).or_else(|| zone("001"))
+    }
+
+    /// Returns the Windows time zone ID of this timezone, if there is one
+    ///
+    /// This is the inverse of [`Tz::from_windows_id`]. Links share the ID of the zone they point to.
+    ///
+    /// ```
+    /// use chrono_tz::Tz;
+    ///
+    /// assert_eq!(Tz::Europe__Paris.windows_id(), Some("Romance Standard Time"));
+    /// assert_eq!(Tz::UTC.windows_id(), Some("UTC"));
+    /// ```
+    pub fn windows_id(&self) -> Option<&'static str> {
+        WINDOWS_IDS.get(self.name()).copied()
+    }
+}
@@ -0,0 +1,61 @@
+/// This test is compiled by `bin/test-filter-config.sh` with the filter config
+/// in `chrono-tz.toml`, which `.cargo/config.toml` points
+/// CHRONO_TZ_FILTER_CONFIG to.
+///
+/// It checks that the zones that are listed, or that are in the countries and
+/// regions that are listed, are kept along with their links, and that the ones
+/// that are excluded are left out.
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use chrono::TimeZone;
+    use chrono_tz::{America, Australia, Europe, Pacific, Tz, TZ_VARIANTS};
+
+    #[test]
+    fn included_zones() {
+        let _london_time = Europe::London.with_ymd_and_hms(2013, 12, 25, 14, 0, 0);
+        assert_eq!(Tz::from_str("Europe/London"), Ok(Europe::London));
+        assert_eq!(Tz::from_str("Europe/Belfast"), Ok(Europe::Belfast));
+
+        // Only one of the zones of Indiana is listed, along with its link.
+        assert_eq!(America::Indiana::Knox.name(), "America/Indiana/Knox");
+        assert_eq!(Tz::from_str("US/Indiana-Starke"), Ok(Tz::US__IndianaStarke));
+        assert!(Tz::from_str("America/Indiana/Indianapolis").is_err());
+
+        assert_eq!(Tz::from_str("UTC"), Ok(Tz::UTC));
+        assert_eq!(Tz::from_str("GMT"), Ok(Tz::GMT));
+    }
+
+    #[test]
+    fn countries() {
+        assert_eq!(Pacific::Auckland.name(), "Pacific/Auckland");
+        assert_eq!(Pacific::Chatham.name(), "Pacific/Chatham");
+        assert_eq!(Tz::from_str("NZ"), Ok(Tz::NZ));
+    }
+
+    #[test]
+    fn regions() {
+        assert_eq!(Australia::Sydney.name(), "Australia/Sydney");
+        assert_eq!(Tz::from_str("Australia/ACT"), Ok(Australia::ACT));
+        assert_eq!(Australia::Perth.name(), "Australia/Perth");
+    }
+
+    #[test]
+    fn excluded_things_are_missing() {
+        // The links to New Zealand's time from Antarctica are excluded, and
+        // the link to Lord Howe Island's goes along with its zone.
+        assert!(Tz::from_str("Antarctica/McMurdo").is_err());
+        assert!(Tz::from_str("Australia/Lord_Howe").is_err());
+        assert!(Tz::from_str("Australia/LHI").is_err());
+
+        assert!(Tz::from_str("Europe/Dublin").is_err());
+        assert!(Tz::from_str("America/New_York").is_err());
+        for tz in TZ_VARIANTS.iter() {
+            assert!(!tz.name().starts_with("Africa"));
+            assert!(!tz.name().starts_with("Antarctica"));
+            assert!(!tz.name().starts_with("Asia"));
+        }
+    }
+}
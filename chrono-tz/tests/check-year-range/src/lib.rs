@@ -0,0 +1,99 @@
+/// This test is compiled by `bin/test-year-range.sh` with the year range set
+/// thusly: CHRONO_TZ_YEAR_RANGE="1970..2060", once with each of the values of
+/// CHRONO_TZ_YEAR_RANGE_EDGES.
+///
+/// It checks that the transitions outside of the range are gone, and that the
+/// offsets beyond its edges are the ones at the edges when they're clamped, or
+/// `-00` when they're unspecified.
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use chrono::{Offset, TimeZone, Utc};
+    use chrono_tz::Europe::London;
+    use chrono_tz::{OffsetComponents, OffsetName, Tz};
+
+    fn unspecified() -> bool {
+        env::var("CHRONO_TZ_YEAR_RANGE_EDGES").map_or(false, |edges| edges == "-00")
+    }
+
+    fn abbreviation(tz: Tz, year: i32, month: u32) -> String {
+        let date = Utc.with_ymd_and_hms(year, month, 1, 12, 0, 0).unwrap();
+        date.with_timezone(&tz).offset().abbreviation().to_owned()
+    }
+
+    #[test]
+    fn within_range() {
+        assert_eq!(abbreviation(London, 1990, 1), "GMT");
+        assert_eq!(abbreviation(London, 1990, 7), "BST");
+        assert_eq!(abbreviation(London, 2059, 7), "BST");
+
+        let summer = Utc.with_ymd_and_hms(2024, 7, 1, 12, 0, 0).unwrap();
+        let offset = summer.with_timezone(&London).offset().clone();
+        assert_eq!(offset.fix().local_minus_utc(), 3600);
+        assert!(offset.is_dst());
+    }
+
+    #[test]
+    fn before_range() {
+        // British Standard Time was in effect all year round at the start of 1970.
+        let (name, offset) = match unspecified() {
+            true => ("-00", 0),
+            false => ("BST", 3600),
+        };
+        for year in [1800, 1900, 1969] {
+            assert_eq!(abbreviation(London, year, 7), name);
+        }
+        let date = Utc.with_ymd_and_hms(1950, 7, 1, 12, 0, 0).unwrap();
+        let local = date.with_timezone(&London);
+        assert_eq!(local.offset().fix().local_minus_utc(), offset);
+
+        let first = London.transitions().next().unwrap();
+        match unspecified() {
+            true => {
+                assert_eq!(first.at, Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap());
+                assert_eq!(first.before.abbreviation(), "-00");
+                assert_eq!(first.after.abbreviation(), "BST");
+            }
+            false => {
+                assert_eq!(first.at, Utc.with_ymd_and_hms(1971, 10, 31, 2, 0, 0).unwrap());
+                assert_eq!(first.after.abbreviation(), "GMT");
+            }
+        }
+    }
+
+    #[test]
+    fn after_range() {
+        let name = match unspecified() {
+            true => "-00",
+            false => "GMT",
+        };
+        for year in [2060, 2070, 2200] {
+            assert_eq!(abbreviation(London, year, 7), name);
+        }
+
+        let last = London.transitions().next_back().unwrap();
+        match unspecified() {
+            true => {
+                assert_eq!(last.at, Utc.with_ymd_and_hms(2060, 1, 1, 0, 0, 0).unwrap());
+                assert_eq!(last.before.abbreviation(), "GMT");
+                assert_eq!(last.after.abbreviation(), "-00");
+            }
+            false => {
+                assert_eq!(last.at, Utc.with_ymd_and_hms(2059, 10, 26, 1, 0, 0).unwrap());
+                assert_eq!(last.after.abbreviation(), "GMT");
+            }
+        }
+    }
+
+    #[test]
+    fn no_transitions_outside_range() {
+        let start = Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2060, 1, 1, 0, 0, 0).unwrap();
+        for tz in chrono_tz::TZ_VARIANTS {
+            assert_eq!(tz.transitions_in(..start).count(), 0, "{tz}");
+            assert!(tz.transitions_in(end..).all(|t| t.at == end), "{tz}");
+        }
+    }
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "exclude-backward-links")]
+
+use chrono_tz::{Tz, TZ_VARIANTS};
+
+#[test]
+fn backward_links_are_aliases() {
+    assert_eq!("US/Eastern".parse(), Ok(Tz::America__New_York));
+    assert_eq!("Asia/Calcutta".parse(), Ok(Tz::Asia__Kolkata));
+    assert_eq!(
+        "Asia/Calcutta".parse::<Tz>().unwrap().name(),
+        "Asia/Kolkata"
+    );
+    assert_eq!("US/Nowhere".parse::<Tz>().ok(), None);
+
+    // `UTC` stays, as `chrono_tz::UTC` is so widely used.
+    assert_eq!(chrono_tz::UTC.name(), "UTC");
+    assert_eq!(
+        TZ_VARIANTS
+            .iter()
+            .filter(|tz| tz.is_deprecated_alias())
+            .collect::<Vec<_>>(),
+        [&Tz::UTC]
+    );
+    assert!(TZ_VARIANTS.iter().all(|tz| tz.name() != "US/Eastern"));
+}
+
+#[test]
+fn data_using_backward_links() {
+    // CLDR and zone.tab still name some zones by their backward links.
+    assert_eq!(
+        Tz::from_windows_id("India Standard Time", None),
+        Some(Tz::Asia__Kolkata)
+    );
+    assert_eq!(Tz::Asia__Kolkata.windows_id(), Some("India Standard Time"));
+    // Unless the data comes with the zones of `backzone`, where Oslo is one.
+    #[cfg(not(chrono_tz_custom_tzdata))]
+    {
+        assert_eq!(chrono_tz::zones_for_country("NO"), [Tz::Europe__Berlin]);
+        assert!(Tz::Europe__Berlin.countries().contains(&"NO"));
+    }
+}
@@ -1,7 +1,7 @@
 use std::fs;
 
 use chrono::{DateTime, Offset, TimeZone};
-use chrono_tz::{IANA_TZDB_VERSION, TZ_VARIANTS};
+use chrono_tz::{OffsetName, TzFile, IANA_TZDB_VERSION, TZ_VARIANTS};
 use tzfile::Tz;
 
 #[test]
@@ -36,3 +36,43 @@ fn tzfile() {
         }
     }
 }
+
+#[test]
+fn tzfile_loader() {
+    let Ok(system_version) = fs::read_to_string("/usr/share/zoneinfo/+VERSION") else {
+        return;
+    };
+
+    if IANA_TZDB_VERSION != system_version.trim() {
+        return;
+    }
+
+    for tz in TZ_VARIANTS {
+        let Ok(file) = TzFile::named(tz.name()) else {
+            continue;
+        };
+
+        // Pre-1970 data may come from the backzone file, so only compare later instants,
+        // including ones that are past the last transition of the file.
+        for seconds_since_epoch in (0..8_000_000_000).step_by(60 * 60 * 24 * 29) {
+            let utc_datetime = DateTime::from_timestamp(seconds_since_epoch, 0)
+                .unwrap()
+                .naive_utc();
+            let from_file = file.offset_from_utc_datetime(&utc_datetime);
+            let built_in = tz.offset_from_utc_datetime(&utc_datetime);
+
+            assert_eq!(
+                from_file.fix(),
+                built_in.fix(),
+                "{seconds_since_epoch} {}",
+                tz.name()
+            );
+            assert_eq!(
+                from_file.abbreviation(),
+                built_in.abbreviation(),
+                "{seconds_since_epoch} {}",
+                tz.name()
+            );
+        }
+    }
+}
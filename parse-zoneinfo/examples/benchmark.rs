@@ -14,6 +14,8 @@ fn main() {
                 Line::Continuation(cont) => builder.add_continuation_line(cont).unwrap(),
                 Line::Rule(rule) => builder.add_rule_line(rule).unwrap(),
                 Line::Link(link) => builder.add_link_line(link).unwrap(),
+                Line::Leap(leap) => builder.add_leap_line(leap).unwrap(),
+                Line::Expires(expires) => builder.add_expires_line(expires).unwrap(),
                 Line::Space => {}
             }
         }
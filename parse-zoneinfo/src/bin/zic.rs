@@ -0,0 +1,80 @@
+//! Compiles a directory of tz source files into a tree of TZif files, much
+//! like the `zic` program that comes with the tz database.
+//!
+//! Usage: `zic <source directory> <output directory>`
+//!
+//! The source directory has to contain the files listed in
+//! `parse_zoneinfo::FILES`. Every zone and link ends up as a separate file
+//! in the output directory, at the path given by its name; links are written
+//! as copies of their target. Mistakes that `parse_zoneinfo::validate` finds
+//! in the source files are printed as warnings.
+
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+use std::process::ExitCode;
+use std::{env, error::Error};
+
+use parse_zoneinfo::table::{ReadError, Table, TableBuilder};
+use parse_zoneinfo::transitions::TableTransitions;
+use parse_zoneinfo::tzif::write_tzif;
+use parse_zoneinfo::validate::Validate;
+use parse_zoneinfo::FILES;
+
+fn main() -> ExitCode {
+    let args = env::args().collect::<Vec<_>>();
+    let [_, source, output] = &args[..] else {
+        eprintln!("usage: zic <source directory> <output directory>");
+        return ExitCode::FAILURE;
+    };
+
+    // Errors in the source files are shown like compiler diagnostics.
+    let table = match read_table(Path::new(source)) {
+        Ok(table) => table,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    for diagnostic in table.validate() {
+        eprintln!("zic: warning: {diagnostic}");
+    }
+
+    match write_tree(&table, Path::new(output)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("zic: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn read_table(source: &Path) -> Result<Table, ReadError> {
+    let mut builder = TableBuilder::new();
+    for name in FILES {
+        let path = source.join(name);
+        let name = path.display().to_string();
+        let file = File::open(&path).map_err(|e| ReadError::Io(name.clone(), e))?;
+        builder.add_file(&name, BufReader::new(file))?;
+    }
+    Ok(builder.build())
+}
+
+fn write_tree(table: &Table, output: &Path) -> Result<(), Box<dyn Error>> {
+    for name in table.zonesets.keys().chain(table.links.keys()) {
+        let timespans = table
+            .timespans(name)
+            .ok_or_else(|| format!("{name}: link to an unknown zone"))?;
+        let footer = table.posix_tz(name);
+
+        let path = output.join(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = BufWriter::new(File::create(&path)?);
+        write_tzif(&mut file, &timespans, footer.as_ref())
+            .map_err(|e| format!("{}: {e}", path.display()))?;
+        file.flush()?;
+    }
+    Ok(())
+}
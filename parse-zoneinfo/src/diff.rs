@@ -0,0 +1,410 @@
+//! Comparing two versions of the time zone data.
+//!
+//! Each release of the tz database adds or removes a few zones and links,
+//! and changes the rules of a few more. What matters to most users is
+//! whether the local times they care about move, so as well as the changes
+//! to the table itself, this module works out the exact instants at which
+//! the computed timespans of each zone start to differ.
+//!
+//! Changes to the past are common too, as historians correct old records,
+//! so both comparisons take a cutoff before which differences are ignored.
+//!
+//! ### Example
+//!
+//! ```
+//! use parse_zoneinfo::diff;
+//! use parse_zoneinfo::line::Line;
+//! use parse_zoneinfo::table::{Table, TableBuilder};
+//!
+//! fn table(lines: &[&str]) -> Table {
+//!     let mut builder = TableBuilder::new();
+//!     for line in lines {
+//!         builder.add_line(Line::new(line).unwrap()).unwrap();
+//!     }
+//!     builder.build()
+//! }
+//!
+//! let old = table(&["Zone  America/Nowhere  -5:00  -  EST"]);
+//! let new = table(&[
+//!     "Zone  America/Nowhere  -5:00  -  EST  2030 Mar 10  2:00",
+//!     "                       -4:00  -  EDT",
+//! ]);
+//!
+//! // 2025-01-01 00:00:00 UTC
+//! let changes = diff::tables(&old, &new, Some(1_735_689_600));
+//! let changes = &changes.timespans["America/Nowhere"];
+//! assert_eq!(changes.len(), 1);
+//! // 2030-03-10 07:00:00 UTC
+//! assert_eq!(changes[0].start, Some(1_899_356_400));
+//! assert_eq!(changes[0].end, None);
+//! assert_eq!((changes[0].old.name.as_str(), changes[0].new.name.as_str()), ("EST", "EDT"));
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::line::Year;
+use crate::table::{RuleInfo, Table};
+use crate::transitions::{FixedTimespan, FixedTimespanSet, TableTransitions};
+
+/// The differences between two tables.
+#[derive(PartialEq, Debug, Default)]
+pub struct TableDiff {
+    /// The names of the zones that are only in the new table.
+    pub added_zones: Vec<String>,
+
+    /// The names of the zones that are only in the old table.
+    pub removed_zones: Vec<String>,
+
+    /// The names of the links that are only in the new table.
+    pub added_links: Vec<String>,
+
+    /// The names of the links that are only in the old table.
+    pub removed_links: Vec<String>,
+
+    /// The rulesets whose rules changed, including ones that were added or
+    /// removed as a whole.
+    pub rulesets: Vec<RulesetDiff>,
+
+    /// The changes to the timespans of each zone or link that’s in both
+    /// tables, leaving out the ones whose timespans stay the same.
+    pub timespans: BTreeMap<String, Vec<TimespanChange>>,
+}
+
+impl TableDiff {
+    /// Returns whether the tables are the same.
+    pub fn is_empty(&self) -> bool {
+        self == &TableDiff::default()
+    }
+}
+
+/// The rules of a ruleset that changed between two tables.
+#[derive(PartialEq, Debug, Clone)]
+pub struct RulesetDiff {
+    /// The name of the ruleset.
+    pub name: String,
+
+    /// The rules that are only in the old table.
+    pub removed: Vec<RuleInfo>,
+
+    /// The rules that are only in the new table.
+    pub added: Vec<RuleInfo>,
+}
+
+/// A stretch of time during which the timespans of a zone differ.
+#[derive(PartialEq, Debug, Clone)]
+pub struct TimespanChange {
+    /// The Unix timestamp at which the difference starts, or `None` if it’s
+    /// there from the beginning.
+    pub start: Option<i64>,
+
+    /// The Unix timestamp at which the difference ends, or `None` if it
+    /// lasts forever.
+    pub end: Option<i64>,
+
+    /// The timespan in effect in the old data.
+    pub old: FixedTimespan,
+
+    /// The timespan in effect in the new data.
+    pub new: FixedTimespan,
+}
+
+/// Compares two tables, ignoring any differences before the cutoff, given
+/// as a Unix timestamp.
+///
+/// Rules are compared as they’re written, so a rule counts as changed if
+/// any of its fields did, even if that doesn’t change any timespans. With a
+/// cutoff, rules that stopped applying before it are left out.
+pub fn tables(old: &Table, new: &Table, cutoff: Option<i64>) -> TableDiff {
+    let (added_zones, removed_zones) =
+        added_and_removed(old.zonesets.keys().collect(), new.zonesets.keys().collect());
+    let (added_links, removed_links) =
+        added_and_removed(old.links.keys().collect(), new.links.keys().collect());
+
+    let names = old.rulesets.keys().chain(new.rulesets.keys());
+    let rulesets = names
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter_map(|name| {
+            let (removed, added) = unmatched(
+                recent(old.rulesets.get(name), cutoff),
+                recent(new.rulesets.get(name), cutoff),
+            );
+            if removed.is_empty() && added.is_empty() {
+                return None;
+            }
+            Some(RulesetDiff {
+                name: name.clone(),
+                removed,
+                added,
+            })
+        })
+        .collect();
+
+    let mut changes = BTreeMap::new();
+    let names = old.zonesets.keys().chain(old.links.keys());
+    for name in names {
+        // Links to missing zones have no timespans to compare.
+        let (Some(old_timespans), Some(new_timespans)) = (old.timespans(name), new.timespans(name))
+        else {
+            continue;
+        };
+        let timespan_changes = timespans(&old_timespans, &new_timespans, cutoff);
+        if !timespan_changes.is_empty() {
+            changes.insert(name.clone(), timespan_changes);
+        }
+    }
+
+    TableDiff {
+        added_zones,
+        removed_zones,
+        added_links,
+        removed_links,
+        rulesets,
+        timespans: changes,
+    }
+}
+
+/// Compares the timespans of a zone in two versions of the data, returning
+/// the stretches of time during which the offsets or abbreviations differ,
+/// in order. Any differences before the cutoff, given as a Unix timestamp,
+/// are ignored, and one that spans it is cut to start at it.
+pub fn timespans(
+    old: &FixedTimespanSet,
+    new: &FixedTimespanSet,
+    cutoff: Option<i64>,
+) -> Vec<TimespanChange> {
+    let start = cutoff.unwrap_or(i64::MIN);
+    let mut instants = old
+        .rest
+        .iter()
+        .chain(&new.rest)
+        .map(|&(instant, _)| instant)
+        .filter(|&instant| instant > start)
+        .collect::<Vec<_>>();
+    instants.push(start);
+    instants.sort_unstable();
+    instants.dedup();
+
+    let mut changes = Vec::<TimespanChange>::new();
+    for (i, &instant) in instants.iter().enumerate() {
+        let (old, new) = (in_effect(old, instant), in_effect(new, instant));
+        if old == new {
+            continue;
+        }
+
+        let start = Some(instant).filter(|&instant| instant != i64::MIN);
+        let end = instants.get(i + 1).copied();
+        match changes.last_mut() {
+            // Only one of the sets changed here, to the same timespan.
+            Some(last) if last.end == start && last.old == *old && last.new == *new => {
+                last.end = end;
+            }
+            _ => changes.push(TimespanChange {
+                start,
+                end,
+                old: old.clone(),
+                new: new.clone(),
+            }),
+        }
+    }
+    changes
+}
+
+/// Returns the timespan of a set in effect at an instant.
+fn in_effect(set: &FixedTimespanSet, instant: i64) -> &FixedTimespan {
+    match set.rest.partition_point(|&(start, _)| start <= instant) {
+        0 => &set.first,
+        index => &set.rest[index - 1].1,
+    }
+}
+
+/// Returns the names only in the new set, then the ones only in the old
+/// set, both sorted.
+fn added_and_removed(old: BTreeSet<&String>, new: BTreeSet<&String>) -> (Vec<String>, Vec<String>) {
+    let added = new.difference(&old).map(|&name| name.clone()).collect();
+    let removed = old.difference(&new).map(|&name| name.clone()).collect();
+    (added, removed)
+}
+
+/// Returns the rules of a ruleset that take effect at or after the cutoff.
+fn recent(rules: Option<&Vec<RuleInfo>>, cutoff: Option<i64>) -> Vec<&RuleInfo> {
+    let rules = rules.map_or(&[][..], |rules| &rules[..]);
+    rules
+        .iter()
+        .filter(|rule| cutoff.map_or(true, |cutoff| applies_since(rule, cutoff)))
+        .collect()
+}
+
+/// Returns the rules only in the old list, then the ones only in the new
+/// list, keeping their order. A rule that appears twice in one list has to
+/// appear twice in the other to match.
+fn unmatched(old: Vec<&RuleInfo>, mut new: Vec<&RuleInfo>) -> (Vec<RuleInfo>, Vec<RuleInfo>) {
+    let mut removed = Vec::new();
+    for rule in old {
+        match new.iter().position(|&other| other == rule) {
+            Some(index) => {
+                let _ = new.remove(index);
+            }
+            None => removed.push(rule.clone()),
+        }
+    }
+    (removed, new.into_iter().cloned().collect())
+}
+
+/// Returns whether a rule takes effect at or after an instant, roughly, as
+/// it ignores the offset of the zone that uses it.
+fn applies_since(rule: &RuleInfo, instant: i64) -> bool {
+    let last_year = match (rule.from_year, rule.to_year) {
+        (_, Some(Year::Maximum)) | (Year::Maximum, None) => return true,
+        (Year::Number(year), None) | (_, Some(Year::Number(year))) => year,
+        (Year::Minimum, None) | (_, Some(Year::Minimum)) => return false,
+    };
+    rule.absolute_datetime(last_year, 0, 0) >= instant
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::line::Line;
+    use crate::table::TableBuilder;
+
+    fn table(lines: &[&str]) -> Table {
+        let mut builder = TableBuilder::new();
+        for line in lines {
+            builder.add_line(Line::new(line).unwrap()).unwrap();
+        }
+        builder.build()
+    }
+
+    fn timespan(utc_offset: i64, dst_offset: i64, name: &str) -> FixedTimespan {
+        FixedTimespan {
+            utc_offset,
+            dst_offset,
+            name: name.to_owned(),
+        }
+    }
+
+    #[test]
+    fn same() {
+        let lines = [
+            "Rule  EU  1981  max  -  Mar  lastSun  1:00u  1:00  S",
+            "Rule  EU  1996  max  -  Oct  lastSun  1:00u  0  -",
+            "Zone  Europe/Paris  1:00  EU  CE%sT",
+            "Link  Europe/Paris  Europe/Monaco",
+        ];
+        assert!(tables(&table(&lines), &table(&lines), None).is_empty());
+    }
+
+    #[test]
+    fn timespan_changes() {
+        let old = FixedTimespanSet {
+            first: timespan(3600, 0, "CET"),
+            rest: vec![
+                (100, timespan(3600, 3600, "CEST")),
+                (200, timespan(3600, 0, "CET")),
+            ],
+        };
+        let new = FixedTimespanSet {
+            first: timespan(3600, 0, "CET"),
+            rest: vec![
+                (150, timespan(3600, 3600, "CEST")),
+                (200, timespan(3600, 0, "CET")),
+                (300, timespan(7200, 0, "EET")),
+            ],
+        };
+        assert_eq!(
+            timespans(&old, &new, None),
+            [
+                TimespanChange {
+                    start: Some(100),
+                    end: Some(150),
+                    old: timespan(3600, 3600, "CEST"),
+                    new: timespan(3600, 0, "CET"),
+                },
+                TimespanChange {
+                    start: Some(300),
+                    end: None,
+                    old: timespan(3600, 0, "CET"),
+                    new: timespan(7200, 0, "EET"),
+                },
+            ]
+        );
+        assert_eq!(
+            timespans(&old, &new, Some(120)),
+            [
+                TimespanChange {
+                    start: Some(120),
+                    end: Some(150),
+                    old: timespan(3600, 3600, "CEST"),
+                    new: timespan(3600, 0, "CET"),
+                },
+                TimespanChange {
+                    start: Some(300),
+                    end: None,
+                    old: timespan(3600, 0, "CET"),
+                    new: timespan(7200, 0, "EET"),
+                },
+            ]
+        );
+        assert_eq!(timespans(&old, &new, Some(150)).len(), 1);
+    }
+
+    #[test]
+    fn from_the_beginning() {
+        let old = FixedTimespanSet {
+            first: timespan(0, 0, "LMT"),
+            rest: vec![(100, timespan(3600, 0, "CET"))],
+        };
+        let new = FixedTimespanSet {
+            first: timespan(60, 0, "LMT"),
+            rest: vec![(100, timespan(3600, 0, "CET"))],
+        };
+        let changes = timespans(&old, &new, None);
+        assert_eq!((changes[0].start, changes[0].end), (None, Some(100)));
+        assert_eq!(changes.len(), 1);
+        assert_eq!(timespans(&old, &new, Some(100)), []);
+    }
+
+    #[test]
+    fn table_changes() {
+        let old = table(&[
+            "Rule  Nowhere  1990  2010  -  Apr  1  2:00  1:00  D",
+            "Rule  Nowhere  1990  2010  -  Oct  1  2:00  0  S",
+            "Zone  America/Nowhere  -5:00  Nowhere  E%sT",
+            "Zone  America/Gone  -6:00  -  CST",
+            "Link  America/Nowhere  America/Elsewhere",
+        ]);
+        let new = table(&[
+            "Rule  Nowhere  1990  2010  -  Apr  1  2:00  1:00  D",
+            "Rule  Nowhere  1990  2009  -  Oct  1  2:00  0  S",
+            "Rule  Nowhere  2010  only  -  Nov  1  2:00  0  S",
+            "Zone  America/Nowhere  -5:00  Nowhere  E%sT",
+            "Zone  America/New  -7:00  -  MST",
+            "Link  America/Nowhere  America/Elsewhere",
+            "Link  America/New  America/Newer",
+        ]);
+
+        let diff = tables(&old, &new, None);
+        assert_eq!(diff.added_zones, ["America/New"]);
+        assert_eq!(diff.removed_zones, ["America/Gone"]);
+        assert_eq!(diff.added_links, ["America/Newer"]);
+        assert!(diff.removed_links.is_empty());
+        assert_eq!(diff.rulesets.len(), 1);
+        assert_eq!(diff.rulesets[0].removed.len(), 1);
+        assert_eq!(diff.rulesets[0].added.len(), 2);
+
+        // 2010-10-01 06:00:00 UTC, when EST now starts a month later.
+        let changes = &diff.timespans["America/Nowhere"];
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].start, Some(1_285_912_800));
+        assert_eq!(changes[0].end, Some(1_288_591_200));
+        assert_eq!(changes[0].old.name, "EST");
+        assert_eq!(changes[0].new.name, "EDT");
+        assert_eq!(diff.timespans["America/Elsewhere"], *changes);
+
+        // 2011-01-01 00:00:00 UTC
+        let diff = tables(&old, &new, Some(1_293_840_000));
+        assert!(diff.rulesets.is_empty());
+        assert!(diff.timespans.is_empty());
+    }
+}
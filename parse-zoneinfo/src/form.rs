@@ -0,0 +1,303 @@
+//! Converting tz source files between the forms they come in.
+//!
+//! The tz database comes in three forms, which all give the same UT offsets
+//! but differ in the features they use:
+//!
+//! - the **main** form, which is how the files are distributed, uses
+//!   negative `SAVE` values so that Irish Standard Time, for example, is
+//!   standard time and winter time is the daylight-saving one, but holds
+//!   back on some other features;
+//! - the **vanguard** form uses every feature the main form might adopt in
+//!   the future, such as a `GMT` zone that isn't a link to `Etc/GMT`;
+//! - the **rearguard** form leaves out features that older parsers lack,
+//!   such as negative `SAVE` values and `%z` formats, so that Irish Standard
+//!   Time is daylight-saving time again.
+//!
+//! The lines that differ are marked with `# Vanguard section` and
+//! `# Rearguard section` comments, with the section that doesn't apply to
+//! the main form commented out. `Converter` rewrites the main form into another
+//! one much like the `ziguard.awk` script of the tz database does, including its
+//! special handling of the Morocco rules, which aren't marked.
+//!
+//! ```
+//! use parse_zoneinfo::form::{Converter, DataForm};
+//!
+//! let mut converter = Converter::new(DataForm::Rearguard);
+//! let lines = [
+//!     "# Vanguard section, for zic and other parsers that support negative DST.",
+//!     "\t1:00\tEire\tIST/GMT",
+//!     "# Rearguard section, for parsers lacking negative DST; see ziguard.awk.",
+//!     "#\t0:00\tEU\tGMT/IST",
+//!     "# End of rearguard section.",
+//! ];
+//! let lines = lines.iter().map(|line| converter.convert(line)).collect::<Vec<_>>();
+//! assert_eq!(lines[1], "#\t1:00\tEire\tIST/GMT");
+//! assert_eq!(lines[3], "\t0:00\tEU\tGMT/IST");
+//! ```
+
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+/// One of the forms of the tz source files.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub enum DataForm {
+    /// The form the files are distributed in.
+    #[default]
+    Main,
+    /// The form with the newest features.
+    Vanguard,
+    /// The form for parsers that lack some of the newer features.
+    Rearguard,
+}
+
+impl DataForm {
+    /// Returns the name of this form, as used by the tz `Makefile`.
+    pub fn name(self) -> &'static str {
+        match self {
+            DataForm::Main => "main",
+            DataForm::Vanguard => "vanguard",
+            DataForm::Rearguard => "rearguard",
+        }
+    }
+}
+
+impl fmt::Display for DataForm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for DataForm {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<DataForm, Error> {
+        match input {
+            "main" => Ok(DataForm::Main),
+            "vanguard" => Ok(DataForm::Vanguard),
+            "rearguard" => Ok(DataForm::Rearguard),
+            _ => Err(Error::UnknownDataForm(input.to_owned())),
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum Error {
+    UnknownDataForm(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::UnknownDataForm(s) => write!(
+                f,
+                "unknown data form \"{}\", expected main, vanguard or rearguard",
+                s
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// The marked section a line is in.
+#[derive(PartialEq, Debug, Copy, Clone)]
+enum Section {
+    None,
+    Vanguard,
+    Rearguard,
+}
+
+/// Rewrites the lines of a source file in the main form into a given form.
+///
+/// Lines have to be passed in order, one file at a time, as the converter
+/// keeps track of the section it's in.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Converter {
+    form: DataForm,
+    section: Section,
+}
+
+impl Converter {
+    /// Creates a converter into the given form.
+    pub fn new(form: DataForm) -> Converter {
+        Converter {
+            form,
+            section: Section::None,
+        }
+    }
+
+    /// Returns the line in the form of this converter, commented out if it
+    /// doesn't belong to it.
+    pub fn convert<'a>(&mut self, line: &'a str) -> Cow<'a, str> {
+        if line.starts_with("# Vanguard section") {
+            self.section = Section::Vanguard;
+        } else if line.starts_with("# Rearguard section") {
+            self.section = Section::Rearguard;
+        } else if line.starts_with("# End of rearguard section") {
+            self.section = Section::None;
+        }
+
+        let keep = match (self.form, self.section) {
+            (DataForm::Main, _) => return Cow::Borrowed(line),
+            (DataForm::Rearguard, Section::None) => return rearguard_morocco(line),
+            (_, Section::None) => return Cow::Borrowed(line),
+            (form, Section::Vanguard) => form == DataForm::Vanguard,
+            (form, Section::Rearguard) => form == DataForm::Rearguard,
+        };
+
+        match line.strip_prefix('#') {
+            // Commented-out data lines have no space after the `#`, unlike
+            // the comments around them.
+            Some(data) if keep && !data.is_empty() && !data.starts_with(' ') => Cow::Borrowed(data),
+            None if !keep && !line.trim().is_empty() => Cow::Owned(format!("#{line}")),
+            _ => Cow::Borrowed(line),
+        }
+    }
+}
+
+/// Changes the Morocco rules and zones of the main form, which observe
+/// negative DST during Ramadan since 2019, into ones that observe positive
+/// DST the rest of the year.
+fn rearguard_morocco(line: &str) -> Cow<'_, str> {
+    let columns = line.split_ascii_whitespace().collect::<Vec<_>>();
+    match columns[..] {
+        // Morocco stayed on +01 instead of falling back in October 2018.
+        ["Rule", "Morocco", _, "2018", _, "Oct", ..] => {
+            Cow::Owned(line.replacen("\t2018\t", "\t2017\t", 1))
+        }
+        ["Rule", "Morocco", from, _, _, _, _, time, save, _] if from >= "2019" => {
+            match (time, save) {
+                ("2:00", "0") => Cow::Owned(line.replacen("\t0\t", "\t1:00\t", 1)),
+                (_, "-1:00") => Cow::Owned(line.replacen("\t-1:00\t", "\t0\t", 1)),
+                _ => Cow::Borrowed(line),
+            }
+        }
+        [offset, "Morocco", _] if !line.starts_with('#') && offset.starts_with(['+', '0', '1']) => {
+            Cow::Owned(line.replacen("1:00\tMorocco", "0:00\tMorocco", 1))
+        }
+        _ => Cow::Borrowed(line),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn convert(form: DataForm, lines: &[&str]) -> Vec<String> {
+        let mut converter = Converter::new(form);
+        lines
+            .iter()
+            .map(|line| converter.convert(line).into_owned())
+            .collect()
+    }
+
+    const WINDHOEK: &[&str] = &[
+        "Zone\tAfrica/Windhoek\t1:08:24 -\tLMT\t1892 Feb 8",
+        "# Vanguard section, for zic and other parsers that support negative DST.",
+        "\t\t\t2:00\tNamibia\t%s",
+        "# Rearguard section, for parsers lacking negative DST; see ziguard.awk.",
+        "#\t\t\t2:00\t-\tCAT\t1994 Mar 21  0:00",
+        "# From Paul Eggert (2017-04-07):",
+        "#\t\t\t1:00\tNamibia\t%s\t2017 Oct 24",
+        "#\t\t\t2:00\t-\tCAT",
+        "# End of rearguard section.",
+        "",
+        "#Rule\tNAME\tFROM",
+    ];
+
+    #[test]
+    fn main() {
+        assert_eq!(convert(DataForm::Main, WINDHOEK), WINDHOEK);
+        assert_eq!(convert(DataForm::Vanguard, WINDHOEK), WINDHOEK);
+    }
+
+    #[test]
+    fn rearguard() {
+        assert_eq!(
+            convert(DataForm::Rearguard, WINDHOEK),
+            [
+                "Zone\tAfrica/Windhoek\t1:08:24 -\tLMT\t1892 Feb 8",
+                "# Vanguard section, for zic and other parsers that support negative DST.",
+                "#\t\t\t2:00\tNamibia\t%s",
+                "# Rearguard section, for parsers lacking negative DST; see ziguard.awk.",
+                "\t\t\t2:00\t-\tCAT\t1994 Mar 21  0:00",
+                "# From Paul Eggert (2017-04-07):",
+                "\t\t\t1:00\tNamibia\t%s\t2017 Oct 24",
+                "\t\t\t2:00\t-\tCAT",
+                "# End of rearguard section.",
+                "",
+                "#Rule\tNAME\tFROM",
+            ]
+        );
+    }
+
+    #[test]
+    fn vanguard() {
+        let lines = [
+            "# Vanguard section, which works with most .zi parsers.",
+            "#Zone\tGMT\t\t0\t-\tGMT",
+            "# Rearguard section, for TZUpdater 2.3.2 and earlier.",
+            "Zone\tEtc/GMT\t\t0\t-\tGMT",
+            "",
+            "Link\tEtc/GMT\t\t\t\tGMT",
+            "# End of rearguard section.",
+        ];
+        assert_eq!(convert(DataForm::Main, &lines), lines);
+        assert_eq!(convert(DataForm::Rearguard, &lines), lines);
+        assert_eq!(
+            convert(DataForm::Vanguard, &lines),
+            [
+                "# Vanguard section, which works with most .zi parsers.",
+                "Zone\tGMT\t\t0\t-\tGMT",
+                "# Rearguard section, for TZUpdater 2.3.2 and earlier.",
+                "#Zone\tEtc/GMT\t\t0\t-\tGMT",
+                "",
+                "#Link\tEtc/GMT\t\t\t\tGMT",
+                "# End of rearguard section.",
+            ]
+        );
+    }
+
+    #[test]
+    fn vanguard_from_rearguard() {
+        let rearguard = convert(DataForm::Rearguard, WINDHOEK);
+        let rearguard = rearguard.iter().map(String::as_str).collect::<Vec<_>>();
+        assert_eq!(convert(DataForm::Vanguard, &rearguard), WINDHOEK);
+    }
+
+    #[test]
+    fn morocco() {
+        let lines = [
+            "Rule\tMorocco\t2013\t2018\t-\tOct\tlastSun\t 3:00\t0\t-",
+            "Rule\tMorocco\t2018\tonly\t-\tJun\t17\t 2:00\t1:00\t-",
+            "Rule\tMorocco\t2019\tonly\t-\tMay\t 5\t 3:00\t-1:00\t-",
+            "Rule\tMorocco\t2019\tonly\t-\tJun\t 9\t 2:00\t0\t-",
+            "\t\t\t 0:00\tMorocco\t%z\t2018 Oct 28  3:00",
+            "\t\t\t 1:00\tMorocco\t%z",
+        ];
+        assert_eq!(convert(DataForm::Main, &lines), lines);
+        assert_eq!(
+            convert(DataForm::Rearguard, &lines),
+            [
+                "Rule\tMorocco\t2013\t2017\t-\tOct\tlastSun\t 3:00\t0\t-",
+                "Rule\tMorocco\t2018\tonly\t-\tJun\t17\t 2:00\t1:00\t-",
+                "Rule\tMorocco\t2019\tonly\t-\tMay\t 5\t 3:00\t0\t-",
+                "Rule\tMorocco\t2019\tonly\t-\tJun\t 9\t 2:00\t1:00\t-",
+                "\t\t\t 0:00\tMorocco\t%z\t2018 Oct 28  3:00",
+                "\t\t\t 0:00\tMorocco\t%z",
+            ]
+        );
+    }
+
+    #[test]
+    fn names() {
+        for form in [DataForm::Main, DataForm::Vanguard, DataForm::Rearguard] {
+            assert_eq!(form.name().parse(), Ok(form));
+        }
+        assert_eq!(
+            "Rearguard".parse::<DataForm>(),
+            Err(Error::UnknownDataForm("Rearguard".to_owned()))
+        );
+    }
+}
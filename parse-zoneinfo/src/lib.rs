@@ -4,8 +4,9 @@
 //!
 //! The zoneinfo database is distributed in one of two formats: a raw text
 //! format with one file per continent, and a compiled binary format with one
-//! file per time zone. This crate deals with the former; for the latter, see
-//! the [`zoneinfo_compiled` crate][zc] instead.
+//! file per time zone. This crate deals with the former, though it can write
+//! out the latter with the `tzif` module; to read compiled files, see the
+//! [`zoneinfo_compiled` crate][zc] instead.
 //!
 //! The database itself is maintained by IANA. For more information, see
 //! [IANA’s page on the time zone database][iana]. You can also find the text
@@ -25,7 +26,32 @@
 //! - **Interpreting** these lines into a complete `Table` is done by the
 //!   `table` module;
 //! - **Calculating transitions** from this table is done by the `transitions`
-//!   module.
+//!   module, with the rules that keep recurring after the last of them
+//!   described by the `posix` module.
+//!
+//! The `tzif` module can then write these transitions out as compiled files,
+//! and the `zic` binary does so for a whole directory of tz source files. The
+//! `source` module goes the other way, writing a table back out as source
+//! text.
+//!
+//! The `diff` module compares two versions of the data, down to the instants
+//! at which the timespans of each zone differ.
+//!
+//! The `validate` module checks a table for mistakes that parse but don’t
+//! make sense, such as links to missing zones or unused rulesets.
+//!
+//! The tables that list the countries each zone is used in are read by the
+//! `tab` module.
+//!
+//! The `form` module can turn the source files into the vanguard or
+//! rearguard forms of the data before they get parsed.
+//!
+//! ## Features
+//!
+//! With the `serde` feature, a `Table` and the types it holds, as well as the
+//! `FixedTimespanSet`s computed from it, can be serialized and deserialized,
+//! so a parsed database can be cached or passed to other tools without
+//! parsing the text files again.
 
 #![warn(missing_copy_implementations)]
 //#![warn(missing_docs)]
@@ -34,10 +60,17 @@
 #![warn(unreachable_pub)]
 #![warn(unused)]
 
+pub mod diff;
+pub mod form;
 pub mod line;
+pub mod posix;
+pub mod source;
 pub mod structure;
+pub mod tab;
 pub mod table;
 pub mod transitions;
+pub mod tzif;
+pub mod validate;
 
 pub const FILES: &[&str] = &[
     "africa",
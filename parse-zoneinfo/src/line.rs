@@ -3,12 +3,19 @@
 //! This module provides functions that take a line of input from a zoneinfo
 //! data file and attempts to parse it, returning the details of the line if
 //! it gets parsed successfully. It classifies them as `Rule`, `Link`,
-//! `Zone`, or `Continuation` lines.
+//! `Zone`, or `Continuation` lines, or as the `Leap` and `Expires` lines of
+//! the `leapseconds` file.
 //!
 //! `Line` is the type that parses and holds zoneinfo line data. To try to
 //! parse a string, use the `Line::from_str` constructor. (This isn’t the
 //! `FromStr` trait, so you can’t use `parse` on a string. Sorry!)
 //!
+//! Like `zic`, it accepts keywords in any case, and abbreviated to any prefix
+//! that isn’t also the prefix of another keyword that could go in the same
+//! place, which is what the compact `tzdata.zi` file uses. Its zone
+//! continuation lines don’t start with whitespace either, and are told apart
+//! by starting with an offset.
+//!
 //! ## Examples
 //!
 //! Parsing a `Rule` line:
@@ -64,6 +71,41 @@
 //!     new:       "Asia/Istanbul",
 //! })));
 //! ```
+//!
+//! Parsing the same `Rule` line as above, from `tzdata.zi`:
+//!
+//! ```
+//! use parse_zoneinfo::line::*;
+//!
+//! let line = Line::new("R E 1977 1980 - Ap Su>=1 1u 1 S");
+//!
+//! assert_eq!(line, Ok(Line::Rule(Rule {
+//!     name:         "E",
+//!     from_year:    Year::Number(1977),
+//!     to_year:      Some(Year::Number(1980)),
+//!     month:        Month::April,
+//!     day:          DaySpec::FirstOnOrAfter(Weekday::Sunday, 1),
+//!     time:         TimeSpec::Hours(1).with_type(TimeType::UTC),
+//!     time_to_add:  TimeSpec::Hours(1),
+//!     letters:      Some("S"),
+//! })));
+//! ```
+//!
+//! Parsing a `Leap` line:
+//!
+//! ```
+//! use parse_zoneinfo::line::*;
+//!
+//! let line = Line::new("Leap  2016  Dec  31  23:59:60  +  S");
+//! assert_eq!(line, Ok(Line::Leap(Leap {
+//!     year:        2016,
+//!     month:       Month::December,
+//!     day:         31,
+//!     time:        TimeSpec::HoursMinutesSeconds(23, 59, 60),
+//!     correction:  LeapCorrection::Added,
+//!     rolling:     false,
+//! })));
+//! ```
 
 use std::fmt;
 use std::str::FromStr;
@@ -82,6 +124,8 @@ pub enum Error {
     NotParsedAsRuleLine,
     NotParsedAsZoneLine,
     NotParsedAsLinkLine,
+    NotParsedAsLeapLine,
+    NotParsedAsExpiresLine,
 }
 
 impl fmt::Display for Error {
@@ -109,12 +153,90 @@ impl fmt::Display for Error {
             Error::NotParsedAsRuleLine => write!(f, "failed to parse line as a rule"),
             Error::NotParsedAsZoneLine => write!(f, "failed to parse line as a zone"),
             Error::NotParsedAsLinkLine => write!(f, "failed to parse line as a link"),
+            Error::NotParsedAsLeapLine => write!(f, "failed to parse line as a leap second"),
+            Error::NotParsedAsExpiresLine => {
+                write!(f, "failed to parse line as a leap second expiry")
+            }
         }
     }
 }
 
 impl std::error::Error for Error {}
 
+impl Error {
+    /// Returns the part of the line that couldn’t be parsed, if the error is
+    /// about one part of it rather than the whole line.
+    pub(crate) fn text(&self) -> Option<&str> {
+        match self {
+            Error::FailedYearParse(s)
+            | Error::FailedMonthParse(s)
+            | Error::FailedWeekdayParse(s)
+            | Error::TypeColumnContainedNonHyphen(s)
+            | Error::CouldNotParseSaving(s)
+            | Error::InvalidDaySpec(s)
+            | Error::InvalidTimeSpecAndType(s)
+            | Error::NonWallClockInTimeSpec(s) => Some(s),
+            Error::InvalidLineType(_)
+            | Error::NotParsedAsRuleLine
+            | Error::NotParsedAsZoneLine
+            | Error::NotParsedAsLinkLine
+            | Error::NotParsedAsLeapLine
+            | Error::NotParsedAsExpiresLine => None,
+        }
+    }
+}
+
+/// Looks up a word in a table of keywords the way `zic` does: ignoring case,
+/// and accepting a prefix of a keyword as long as it isn’t the prefix of
+/// another one too.
+fn byword<T: Copy>(word: &str, table: &[(&str, T)]) -> Option<T> {
+    if word.is_empty() {
+        return None;
+    }
+    if let Some(&(_, value)) = table
+        .iter()
+        .find(|(keyword, _)| keyword.eq_ignore_ascii_case(word))
+    {
+        return Some(value);
+    }
+
+    let mut found = table.iter().filter(|(keyword, _)| {
+        keyword
+            .get(..word.len())
+            .map_or(false, |prefix| prefix.eq_ignore_ascii_case(word))
+    });
+    match (found.next(), found.next()) {
+        (Some(&(_, value)), None) => Some(value),
+        _ => None,
+    }
+}
+
+/// The keyword at the start of a line, which says what kind of line it is.
+#[derive(PartialEq, Debug, Copy, Clone)]
+enum LineType {
+    Rule,
+    Zone,
+    Link,
+    Leap,
+    Expires,
+}
+
+impl LineType {
+    fn from_word(word: &str) -> Option<LineType> {
+        // The `leapseconds` file is separate, so `zic` doesn’t count its
+        // keywords when telling whether an abbreviation such as `L` is
+        // ambiguous.
+        const DATA: &[(&str, LineType)] = &[
+            ("Rule", LineType::Rule),
+            ("Zone", LineType::Zone),
+            ("Link", LineType::Link),
+        ];
+        const LEAP: &[(&str, LineType)] =
+            &[("Leap", LineType::Leap), ("Expires", LineType::Expires)];
+        byword(word, DATA).or_else(|| byword(word, LEAP))
+    }
+}
+
 /// A **year** definition field.
 ///
 /// A year has one of the following representations in a file:
@@ -125,6 +247,7 @@ impl std::error::Error for Error {}
 ///   apply after the last rule with a specific year;
 /// - a year number, referring to a specific year.
 #[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Year {
     /// The minimum year possible: `min` or `minimum`.
     Minimum,
@@ -138,20 +261,32 @@ impl FromStr for Year {
     type Err = Error;
 
     fn from_str(input: &str) -> Result<Year, Self::Err> {
-        Ok(match &*input.to_ascii_lowercase() {
-            "min" | "minimum" => Year::Minimum,
-            "max" | "maximum" => Year::Maximum,
-            year => match year.parse() {
-                Ok(year) => Year::Number(year),
-                Err(_) => return Err(Error::FailedYearParse(input.to_string())),
-            },
-        })
+        const YEARS: &[(&str, Year)] = &[("minimum", Year::Minimum), ("maximum", Year::Maximum)];
+        if let Some(year) = byword(input, YEARS) {
+            return Ok(year);
+        }
+
+        match input.parse() {
+            Ok(year) => Ok(Year::Number(year)),
+            Err(_) => Err(Error::FailedYearParse(input.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Year {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Year::Minimum => f.write_str("min"),
+            Year::Maximum => f.write_str("max"),
+            Year::Number(year) => write!(f, "{}", year),
+        }
     }
 }
 
 /// A **month** field, which is actually just a wrapper around
 /// `datetime::Month`.
 #[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Month {
     January = 1,
     February = 2,
@@ -168,7 +303,7 @@ pub enum Month {
 }
 
 impl Month {
-    fn length(self, is_leap: bool) -> i8 {
+    pub(crate) fn length(self, is_leap: bool) -> i8 {
         match self {
             Month::January => 31,
             Month::February if is_leap => 29,
@@ -228,20 +363,41 @@ impl FromStr for Month {
 
     /// Attempts to parse the given string into a value of this type.
     fn from_str(input: &str) -> Result<Month, Self::Err> {
-        Ok(match &*input.to_ascii_lowercase() {
-            "jan" | "january" => Month::January,
-            "feb" | "february" => Month::February,
-            "mar" | "march" => Month::March,
-            "apr" | "april" => Month::April,
-            "may" => Month::May,
-            "jun" | "june" => Month::June,
-            "jul" | "july" => Month::July,
-            "aug" | "august" => Month::August,
-            "sep" | "september" => Month::September,
-            "oct" | "october" => Month::October,
-            "nov" | "november" => Month::November,
-            "dec" | "december" => Month::December,
-            other => return Err(Error::FailedMonthParse(other.to_string())),
+        const MONTHS: &[(&str, Month)] = &[
+            ("January", Month::January),
+            ("February", Month::February),
+            ("March", Month::March),
+            ("April", Month::April),
+            ("May", Month::May),
+            ("June", Month::June),
+            ("July", Month::July),
+            ("August", Month::August),
+            ("September", Month::September),
+            ("October", Month::October),
+            ("November", Month::November),
+            ("December", Month::December),
+        ];
+        byword(input, MONTHS).ok_or_else(|| Error::FailedMonthParse(input.to_ascii_lowercase()))
+    }
+}
+
+impl fmt::Display for Month {
+    /// Writes the month as the three-letter abbreviation the tz source files
+    /// use, such as `Jan`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Month::January => "Jan",
+            Month::February => "Feb",
+            Month::March => "Mar",
+            Month::April => "Apr",
+            Month::May => "May",
+            Month::June => "Jun",
+            Month::July => "Jul",
+            Month::August => "Aug",
+            Month::September => "Sep",
+            Month::October => "Oct",
+            Month::November => "Nov",
+            Month::December => "Dec",
         })
     }
 }
@@ -249,6 +405,7 @@ impl FromStr for Month {
 /// A **weekday** field, which is actually just a wrapper around
 /// `datetime::Weekday`.
 #[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Weekday {
     Sunday,
     Monday,
@@ -282,15 +439,31 @@ impl FromStr for Weekday {
     type Err = Error;
 
     fn from_str(input: &str) -> Result<Weekday, Self::Err> {
-        Ok(match &*input.to_ascii_lowercase() {
-            "mon" | "monday" => Weekday::Monday,
-            "tue" | "tuesday" => Weekday::Tuesday,
-            "wed" | "wednesday" => Weekday::Wednesday,
-            "thu" | "thursday" => Weekday::Thursday,
-            "fri" | "friday" => Weekday::Friday,
-            "sat" | "saturday" => Weekday::Saturday,
-            "sun" | "sunday" => Weekday::Sunday,
-            other => return Err(Error::FailedWeekdayParse(other.to_string())),
+        const WEEKDAYS: &[(&str, Weekday)] = &[
+            ("Monday", Weekday::Monday),
+            ("Tuesday", Weekday::Tuesday),
+            ("Wednesday", Weekday::Wednesday),
+            ("Thursday", Weekday::Thursday),
+            ("Friday", Weekday::Friday),
+            ("Saturday", Weekday::Saturday),
+            ("Sunday", Weekday::Sunday),
+        ];
+        byword(input, WEEKDAYS).ok_or_else(|| Error::FailedWeekdayParse(input.to_ascii_lowercase()))
+    }
+}
+
+impl fmt::Display for Weekday {
+    /// Writes the weekday as the three-letter abbreviation the tz source
+    /// files use, such as `Sun`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Weekday::Sunday => "Sun",
+            Weekday::Monday => "Mon",
+            Weekday::Tuesday => "Tue",
+            Weekday::Wednesday => "Wed",
+            Weekday::Thursday => "Thu",
+            Weekday::Friday => "Fri",
+            Weekday::Saturday => "Sat",
         })
     }
 }
@@ -304,6 +477,7 @@ impl FromStr for Weekday {
 /// Note that in the last example, it’s allowed for that particular Friday to
 /// *be* the 13th in question.
 #[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DaySpec {
     /// A specific day of the month, given by its number.
     Ordinal(i8),
@@ -387,26 +561,23 @@ impl FromStr for DaySpec {
             return Ok(DaySpec::Ordinal(input.parse().unwrap()));
         }
         // Check if it starts with ‘last’, and trim off the first four bytes if it does
-        else if let Some(remainder) = input.strip_prefix("last") {
+        else if let Some(remainder) = input
+            .get(..4)
+            .filter(|last| last.eq_ignore_ascii_case("last"))
+            .map(|_| &input[4..])
+        {
             let weekday = remainder.parse()?;
             return Ok(DaySpec::Last(weekday));
         }
 
-        let weekday = match input.get(..3) {
-            Some(wd) => Weekday::from_str(wd)?,
-            None => return Err(Error::InvalidDaySpec(input.to_string())),
-        };
-
-        let dir = match input.get(3..5) {
-            Some(">=") => true,
-            Some("<=") => false,
+        let (weekday, dir, day) = match (input.split_once(">="), input.split_once("<=")) {
+            (Some((weekday, day)), _) => (weekday, true, day),
+            (_, Some((weekday, day))) => (weekday, false, day),
             _ => return Err(Error::InvalidDaySpec(input.to_string())),
         };
 
-        let day = match input.get(5..) {
-            Some(day) => u8::from_str(day).map_err(|_| Error::InvalidDaySpec(input.to_string()))?,
-            None => return Err(Error::InvalidDaySpec(input.to_string())),
-        } as i8;
+        let weekday = Weekday::from_str(weekday)?;
+        let day = u8::from_str(day).map_err(|_| Error::InvalidDaySpec(input.to_string()))? as i8;
 
         Ok(match dir {
             true => DaySpec::FirstOnOrAfter(weekday, day),
@@ -415,6 +586,17 @@ impl FromStr for DaySpec {
     }
 }
 
+impl fmt::Display for DaySpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DaySpec::Ordinal(day) => write!(f, "{}", day),
+            DaySpec::Last(weekday) => write!(f, "last{}", weekday),
+            DaySpec::LastOnOrBefore(weekday, day) => write!(f, "{}<={}", weekday, day),
+            DaySpec::FirstOnOrAfter(weekday, day) => write!(f, "{}>={}", weekday, day),
+        }
+    }
+}
+
 fn is_leap(year: i64) -> bool {
     // Leap year rules: years which are factors of 4, except those divisible
     // by 100, unless they are divisible by 400.
@@ -437,6 +619,7 @@ fn is_leap(year: i64) -> bool {
 /// Hour 0 is midnight at the start of the day, and Hour 24 is midnight at the
 /// end of the day.
 #[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TimeSpec {
     /// A number of hours.
     Hours(i8),
@@ -481,13 +664,18 @@ impl FromStr for TimeSpec {
                     i8::from_str(hour)
                         .map_err(|_| Error::InvalidTimeSpecAndType(input.to_string()))?,
                 ),
-                (TimeSpec::Hours(hours), minutes) if minutes.len() == 2 => TimeSpec::HoursMinutes(
-                    hours,
-                    i8::from_str(minutes)
-                        .map_err(|_| Error::InvalidTimeSpecAndType(input.to_string()))?
-                        * neg,
-                ),
-                (TimeSpec::HoursMinutes(hours, minutes), seconds) if seconds.len() == 2 => {
+                // `tzdata.zi` leaves out leading zeros, as in `0:9:21`.
+                (TimeSpec::Hours(hours), minutes) if (1..=2).contains(&minutes.len()) => {
+                    TimeSpec::HoursMinutes(
+                        hours,
+                        i8::from_str(minutes)
+                            .map_err(|_| Error::InvalidTimeSpecAndType(input.to_string()))?
+                            * neg,
+                    )
+                }
+                (TimeSpec::HoursMinutes(hours, minutes), seconds)
+                    if (1..=2).contains(&seconds.len()) =>
+                {
                     TimeSpec::HoursMinutesSeconds(
                         hours,
                         minutes,
@@ -504,7 +692,30 @@ impl FromStr for TimeSpec {
     }
 }
 
+impl fmt::Display for TimeSpec {
+    /// Writes the time with two-digit minutes and seconds, and a sign in
+    /// front if it’s negative, such as `-0:30`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (hours, minutes, seconds) = match *self {
+            TimeSpec::Zero => return f.write_str("0"),
+            TimeSpec::Hours(h) => (h, None, None),
+            TimeSpec::HoursMinutes(h, m) => (h, Some(m), None),
+            TimeSpec::HoursMinutesSeconds(h, m, s) => (h, Some(m), Some(s)),
+        };
+        // Every component of a negative time is negative.
+        if hours < 0 || minutes.unwrap_or(0) < 0 || seconds.unwrap_or(0) < 0 {
+            f.write_str("-")?;
+        }
+        write!(f, "{}", hours.unsigned_abs())?;
+        for component in [minutes, seconds].into_iter().flatten() {
+            write!(f, ":{:02}", component.unsigned_abs())?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TimeType {
     Wall,
     Standard,
@@ -523,6 +734,7 @@ impl TimeType {
 }
 
 #[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TimeSpecAndType(pub TimeSpec, pub TimeType);
 
 impl FromStr for TimeSpecAndType {
@@ -545,6 +757,19 @@ impl FromStr for TimeSpecAndType {
     }
 }
 
+impl fmt::Display for TimeSpecAndType {
+    /// Writes the time followed by the letter of its type, which is left out
+    /// for wall clock time.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let TimeSpecAndType(spec, ty) = self;
+        match ty {
+            TimeType::Wall => write!(f, "{}", spec),
+            TimeType::Standard => write!(f, "{}s", spec),
+            TimeType::UTC => write!(f, "{}u", spec),
+        }
+    }
+}
+
 /// The time at which the rules change for a location.
 ///
 /// This is described with as few units as possible: a change that occurs at
@@ -552,6 +777,7 @@ impl FromStr for TimeSpecAndType {
 /// particular day has to list the year, month, and day, and one that occurs
 /// at a particular second has to list everything.
 #[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ChangeTime {
     /// The earliest point in a particular **year**.
     UntilYear(Year),
@@ -684,6 +910,21 @@ impl ChangeTime {
     }
 }
 
+impl fmt::Display for ChangeTime {
+    /// Writes the fields of the time separated by spaces, such as
+    /// `1944 Jan 1 0:01`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChangeTime::UntilYear(year) => write!(f, "{}", year),
+            ChangeTime::UntilMonth(year, month) => write!(f, "{} {}", year, month),
+            ChangeTime::UntilDay(year, month, day) => write!(f, "{} {} {}", year, month, day),
+            ChangeTime::UntilTime(year, month, day, time) => {
+                write!(f, "{} {} {} {}", year, month, day, time)
+            }
+        }
+    }
+}
+
 /// The information contained in both zone lines *and* zone continuation lines.
 #[derive(PartialEq, Debug, Copy, Clone)]
 pub struct ZoneInfo<'a> {
@@ -848,6 +1089,18 @@ impl<'a> ZoneInfo<'a> {
     }
 }
 
+impl<'a> fmt::Display for ZoneInfo<'a> {
+    /// Writes the `STDOFF`, `RULES`, `FORMAT` and `UNTIL` columns separated by
+    /// tabs.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}\t{}\t{}", self.utc_offset, self.saving, self.format)?;
+        match self.time {
+            Some(time) => write!(f, "\t{}", time),
+            None => Ok(()),
+        }
+    }
+}
+
 enum ZoneInfoState<'a> {
     Start,
     Save {
@@ -894,7 +1147,8 @@ pub enum Saving<'a> {
     NoSaving,
     /// This amount of time should be saved while this timespan is in effect.
     /// (This is the equivalent to there being a single one-off rule with the
-    /// given amount of time to save).
+    /// given amount of time to save). It's negative for the negative DST of
+    /// the main and vanguard forms of the data.
     OneOff(TimeSpec),
     /// All rules with the given name should apply while this timespan is in
     /// effect.
@@ -918,6 +1172,16 @@ impl<'a> Saving<'a> {
     }
 }
 
+impl<'a> fmt::Display for Saving<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Saving::NoSaving => f.write_str("-"),
+            Saving::OneOff(time) => write!(f, "{}", time),
+            Saving::Multiple(name) => f.write_str(name),
+        }
+    }
+}
+
 /// A **rule** definition line.
 ///
 /// According to the `zic(8)` man page, a rule line has this form, along with
@@ -953,6 +1217,12 @@ pub struct Rule<'a> {
 
 impl<'a> Rule<'a> {
     fn from_str(input: &'a str) -> Result<Self, Error> {
+        const END_YEARS: &[(&str, Option<Year>)] = &[
+            ("only", None),
+            ("minimum", Some(Year::Minimum)),
+            ("maximum", Some(Year::Maximum)),
+        ];
+
         let mut state = RuleState::Start;
         // Not handled: quoted strings, parts of which are allowed to contain whitespace.
         // Extra complexity does not seem worth it while they don't seem to be used in practice.
@@ -962,7 +1232,11 @@ impl<'a> Rule<'a> {
             }
 
             state = match (state, part) {
-                (RuleState::Start, "Rule") => RuleState::Name,
+                (RuleState::Start, keyword)
+                    if LineType::from_word(keyword) == Some(LineType::Rule) =>
+                {
+                    RuleState::Name
+                }
                 (RuleState::Name, name) => RuleState::FromYear { name },
                 (RuleState::FromYear { name }, year) => RuleState::ToYear {
                     name,
@@ -973,9 +1247,9 @@ impl<'a> Rule<'a> {
                     from_year,
                     // The end year can be ‘only’ to indicate that this rule only
                     // takes place on that year.
-                    to_year: match year {
-                        "only" => None,
-                        _ => Some(Year::from_str(year)?),
+                    to_year: match byword(year, END_YEARS) {
+                        Some(to_year) => to_year,
+                        None => Some(Year::from_str(year)?),
                     },
                 },
                 // According to the spec, the only value inside the ‘type’ column
@@ -1095,6 +1369,25 @@ impl<'a> Rule<'a> {
     }
 }
 
+impl<'a> fmt::Display for Rule<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Rule\t{}\t{}\t", self.name, self.from_year)?;
+        match self.to_year {
+            Some(year) => write!(f, "{}", year)?,
+            None => f.write_str("only")?,
+        }
+        write!(
+            f,
+            "\t-\t{}\t{}\t{}\t{}\t{}",
+            self.month,
+            self.day,
+            self.time,
+            self.time_to_add,
+            self.letters.unwrap_or("-")
+        )
+    }
+}
+
 enum RuleState<'a> {
     Start,
     Name,
@@ -1177,7 +1470,7 @@ pub struct Zone<'a> {
 impl<'a> Zone<'a> {
     fn from_str(input: &'a str) -> Result<Self, Error> {
         let mut iter = input.split_ascii_whitespace();
-        if iter.next() != Some("Zone") {
+        if iter.next().and_then(LineType::from_word) != Some(LineType::Zone) {
             return Err(Error::NotParsedAsZoneLine);
         }
 
@@ -1193,6 +1486,12 @@ impl<'a> Zone<'a> {
     }
 }
 
+impl<'a> fmt::Display for Zone<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Zone\t{}\t{}", self.name, self.info)
+    }
+}
+
 #[derive(PartialEq, Debug, Copy, Clone)]
 pub struct Link<'a> {
     pub existing: &'a str,
@@ -1202,7 +1501,7 @@ pub struct Link<'a> {
 impl<'a> Link<'a> {
     fn from_str(input: &'a str) -> Result<Self, Error> {
         let mut iter = input.split_ascii_whitespace();
-        if iter.next() != Some("Link") {
+        if iter.next().and_then(LineType::from_word) != Some(LineType::Link) {
             return Err(Error::NotParsedAsLinkLine);
         }
 
@@ -1213,6 +1512,204 @@ impl<'a> Link<'a> {
     }
 }
 
+impl<'a> fmt::Display for Link<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Link\t{}\t{}", self.existing, self.new)
+    }
+}
+
+/// A **leap second** line, from the `leapseconds` file.
+///
+/// According to the `zic(8)` man page, a leap line has this form, along with
+/// an example:
+///
+/// ```text
+///     Leap  YEAR  MONTH  DAY  HH:MM:SS  CORR  R/S
+///     Leap  2016  Dec    31   23:59:60  +     S
+/// ```
+///
+/// The example says that a second was inserted after 23:59:59 on the 31st of
+/// December 2016.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Leap {
+    /// The year of the leap second.
+    pub year: i64,
+    /// The month of the leap second.
+    pub month: Month,
+    /// The day of the month of the leap second.
+    pub day: i8,
+    /// The time of the leap second, which is 23:59:60 for an added second
+    /// and 23:59:59 for a removed one.
+    pub time: TimeSpec,
+    /// Whether a second was added or removed.
+    pub correction: LeapCorrection,
+    /// Whether the time is given as the local wall clock time of each zone
+    /// (`R`, for rolling), rather than in UTC (`S`, for stationary).
+    pub rolling: bool,
+}
+
+impl Leap {
+    fn from_str(input: &str) -> Result<Self, Error> {
+        let mut iter = input.split_ascii_whitespace();
+        if iter.next().and_then(LineType::from_word) != Some(LineType::Leap) {
+            return Err(Error::NotParsedAsLeapLine);
+        }
+
+        let (year, month, day, time) = date_time(&mut iter).ok_or(Error::NotParsedAsLeapLine)?;
+        let correction = match iter.next() {
+            Some("+") => LeapCorrection::Added,
+            Some("-") => LeapCorrection::Removed,
+            _ => return Err(Error::NotParsedAsLeapLine),
+        };
+        let rolling = match iter.next() {
+            Some("R") => true,
+            Some("S") => false,
+            _ => return Err(Error::NotParsedAsLeapLine),
+        };
+        if iter.next().is_some() {
+            return Err(Error::NotParsedAsLeapLine);
+        }
+
+        Ok(Leap {
+            year,
+            month,
+            day,
+            time,
+            correction,
+            rolling,
+        })
+    }
+
+    /// Returns the number of seconds since the Unix epoch from which the
+    /// difference between UTC and TAI includes this leap second, which is the
+    /// start of the day after it.
+    ///
+    /// A rolling leap second is taken to happen at the given time in UTC.
+    pub fn timestamp(&self) -> i64 {
+        let time = ChangeTime::UntilTime(
+            Year::Number(self.year),
+            self.month,
+            DaySpec::Ordinal(self.day),
+            self.time.with_type(TimeType::UTC),
+        );
+        // The removed second ends, rather than starts, at the given time.
+        match self.correction {
+            LeapCorrection::Added => time.to_timestamp(0, 0),
+            LeapCorrection::Removed => time.to_timestamp(0, 0) + 1,
+        }
+    }
+}
+
+impl fmt::Display for Leap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let correction = match self.correction {
+            LeapCorrection::Added => '+',
+            LeapCorrection::Removed => '-',
+        };
+        let rolling = if self.rolling { 'R' } else { 'S' };
+        write!(
+            f,
+            "Leap\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.year, self.month, self.day, self.time, correction, rolling
+        )
+    }
+}
+
+/// Whether a leap second adds a second to UTC, or removes one from it.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum LeapCorrection {
+    /// A second was inserted, so that UTC fell a further second behind TAI.
+    Added,
+    /// A second was skipped. This has never happened so far.
+    Removed,
+}
+
+/// An **expires** line, from the `leapseconds` file, giving the time until
+/// which the list of leap seconds is known to be complete.
+///
+/// ```text
+///     Expires  YEAR  MONTH  DAY  HH:MM:SS
+///     Expires  2026  Jun    28   00:00:00
+/// ```
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Expires {
+    /// The year of the expiry.
+    pub year: i64,
+    /// The month of the expiry.
+    pub month: Month,
+    /// The day of the month of the expiry.
+    pub day: i8,
+    /// The time of the expiry, in UTC.
+    pub time: TimeSpec,
+}
+
+impl Expires {
+    fn from_str(input: &str) -> Result<Self, Error> {
+        let mut iter = input.split_ascii_whitespace();
+        if iter.next().and_then(LineType::from_word) != Some(LineType::Expires) {
+            return Err(Error::NotParsedAsExpiresLine);
+        }
+
+        let (year, month, day, time) = date_time(&mut iter).ok_or(Error::NotParsedAsExpiresLine)?;
+        if iter.next().is_some() {
+            return Err(Error::NotParsedAsExpiresLine);
+        }
+
+        Ok(Expires {
+            year,
+            month,
+            day,
+            time,
+        })
+    }
+
+    /// Returns the number of seconds since the Unix epoch at which the list
+    /// of leap seconds expires.
+    pub fn timestamp(&self) -> i64 {
+        ChangeTime::UntilTime(
+            Year::Number(self.year),
+            self.month,
+            DaySpec::Ordinal(self.day),
+            self.time.with_type(TimeType::UTC),
+        )
+        .to_timestamp(0, 0)
+    }
+}
+
+impl fmt::Display for Expires {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Expires\t{}\t{}\t{}\t{}",
+            self.year, self.month, self.day, self.time
+        )
+    }
+}
+
+/// Parses the `YEAR MONTH DAY HH:MM:SS` columns shared by `Leap` and
+/// `Expires` lines.
+fn date_time<'a>(iter: &mut impl Iterator<Item = &'a str>) -> Option<(i64, Month, i8, TimeSpec)> {
+    let year = match Year::from_str(iter.next()?).ok()? {
+        Year::Number(year) => year,
+        _ => return None,
+    };
+    let month = Month::from_str(iter.next()?).ok()?;
+    let day = iter.next()?.parse::<i8>().ok()?;
+    if day < 1 || day > month.length(is_leap(year)) {
+        return None;
+    }
+
+    let time = match TimeSpec::from_str(iter.next()?).ok()? {
+        TimeSpec::HoursMinutesSeconds(h, m, s)
+            if (0..24).contains(&h) && (0..60).contains(&m) && (0..=60).contains(&s) =>
+        {
+            TimeSpec::HoursMinutesSeconds(h, m, s)
+        }
+        _ => return None,
+    };
+    Some((year, month, day, time))
+}
+
 #[derive(PartialEq, Debug, Copy, Clone)]
 pub enum Line<'a> {
     /// This line is empty.
@@ -1225,6 +1722,10 @@ pub enum Line<'a> {
     Rule(Rule<'a>),
     /// This line contains a **link** definition.
     Link(Link<'a>),
+    /// This line contains a **leap second** definition.
+    Leap(Leap),
+    /// This line contains the **expiry** of the leap second list.
+    Expires(Expires),
 }
 
 impl<'a> Line<'a> {
@@ -1236,29 +1737,47 @@ impl<'a> Line<'a> {
             None => input,
         };
 
-        if input.trim().is_empty() {
-            return Ok(Line::Space);
-        }
-
-        if input.starts_with("Zone") {
-            return Ok(Line::Zone(Zone::from_str(input)?));
-        }
+        let first = match input.split_ascii_whitespace().next() {
+            Some(first) => first,
+            None => return Ok(Line::Space),
+        };
 
-        if input.starts_with(&[' ', '\t'][..]) {
+        // Continuation lines are indented in the source files, but not in
+        // `tzdata.zi`, where they start with an offset instead of a keyword.
+        if input.starts_with(&[' ', '\t'][..])
+            || first.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '+')
+        {
             return Ok(Line::Continuation(ZoneInfo::from_iter(
                 input.split_ascii_whitespace(),
             )?));
         }
 
-        if input.starts_with("Rule") {
-            return Ok(Line::Rule(Rule::from_str(input)?));
+        match LineType::from_word(first) {
+            Some(LineType::Zone) => Ok(Line::Zone(Zone::from_str(input)?)),
+            Some(LineType::Rule) => Ok(Line::Rule(Rule::from_str(input)?)),
+            Some(LineType::Link) => Ok(Line::Link(Link::from_str(input)?)),
+            Some(LineType::Leap) => Ok(Line::Leap(Leap::from_str(input)?)),
+            Some(LineType::Expires) => Ok(Line::Expires(Expires::from_str(input)?)),
+            None => Err(Error::InvalidLineType(input.to_string())),
         }
+    }
+}
 
-        if input.starts_with("Link") {
-            return Ok(Line::Link(Link::from_str(input)?));
+impl<'a> fmt::Display for Line<'a> {
+    /// Writes the line back in the syntax of the tz source files, with its
+    /// columns separated by tabs and continuation lines indented by three of
+    /// them, as in the source files. `Line::new` parses this back into the
+    /// same line.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Line::Space => Ok(()),
+            Line::Zone(zone) => write!(f, "{}", zone),
+            Line::Continuation(info) => write!(f, "\t\t\t{}", info),
+            Line::Rule(rule) => write!(f, "{}", rule),
+            Line::Link(link) => write!(f, "{}", link),
+            Line::Leap(leap) => write!(f, "{}", leap),
+            Line::Expires(expires) => write!(f, "{}", expires),
         }
-
-        Err(Error::InvalidLineType(input.to_string()))
     }
 }
 
@@ -1516,6 +2035,56 @@ mod tests {
         },
     })));
 
+    test!(leap: "Leap	1972	Jun	30	23:59:60	+	S" => Ok(Line::Leap(Leap {
+        year:        1972,
+        month:       Month::June,
+        day:         30,
+        time:        TimeSpec::HoursMinutesSeconds(23, 59, 60),
+        correction:  LeapCorrection::Added,
+        rolling:     false,
+    })));
+
+    test!(leap_removed: "Leap 2029 Dec 31 23:59:59 - R" => Ok(Line::Leap(Leap {
+        year:        2029,
+        month:       Month::December,
+        day:         31,
+        time:        TimeSpec::HoursMinutesSeconds(23, 59, 59),
+        correction:  LeapCorrection::Removed,
+        rolling:     true,
+    })));
+
+    test!(expires: "Expires 2026	Jun	28	00:00:00" => Ok(Line::Expires(Expires {
+        year:        2026,
+        month:       Month::June,
+        day:         28,
+        time:        TimeSpec::HoursMinutesSeconds(0, 0, 0),
+    })));
+
+    test!(leap_bad_day:        "Leap 1972 Jun 31 23:59:60 + S" => Err(Error::NotParsedAsLeapLine));
+    test!(leap_bad_correction: "Leap 1972 Jun 30 23:59:60 1 S" => Err(Error::NotParsedAsLeapLine));
+    test!(leap_short_time:     "Leap 1972 Jun 30 23:59 + S"    => Err(Error::NotParsedAsLeapLine));
+    test!(expires_extra:       "Expires 2026 Jun 28 00:00:00 S" => Err(Error::NotParsedAsExpiresLine));
+
+    #[test]
+    fn leap_timestamp() {
+        let Ok(Line::Leap(leap)) = Line::new("Leap 1972 Jun 30 23:59:60 + S") else {
+            panic!("not a leap line");
+        };
+        // 1972-07-01 00:00:00 UTC
+        assert_eq!(leap.timestamp(), 78796800);
+        let removed = Leap {
+            time: TimeSpec::HoursMinutesSeconds(23, 59, 59),
+            correction: LeapCorrection::Removed,
+            ..leap
+        };
+        assert_eq!(removed.timestamp(), 78796800);
+
+        let Ok(Line::Expires(expires)) = Line::new("Expires 2026 Jun 28 00:00:00") else {
+            panic!("not an expires line");
+        };
+        assert_eq!(expires.timestamp(), 1782604800);
+    }
+
     #[test]
     fn negative_offsets() {
         static LINE: &str = "Zone    Europe/London   -0:01:15 -  LMT 1847 Dec  1  0:00s";
@@ -1537,6 +2106,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn negative_saving() {
+        static LINE: &str = "\t\t\t1:00\t-1:00\tGMT\t1947 Feb 23  2:00";
+        let Ok(Line::Continuation(info)) = Line::new(LINE) else {
+            panic!("not a continuation line");
+        };
+        assert_eq!(info.saving, Saving::OneOff(TimeSpec::HoursMinutes(-1, 0)));
+    }
+
+    #[test]
+    fn display() {
+        for (input, output) in [
+            (
+                "Rule US 1967 1973 - Apr lastSun 2:00 1:00 D",
+                "Rule\tUS\t1967\t1973\t-\tApr\tlastSun\t2:00\t1:00\tD",
+            ),
+            (
+                "R Morocco 2019 o - May 5 3 -1 -",
+                "Rule\tMorocco\t2019\tonly\t-\tMay\t5\t3\t-1\t-",
+            ),
+            (
+                "Zone America/Danmarkshavn -1:14:40 - LMT 1916 Jul 28",
+                "Zone\tAmerica/Danmarkshavn\t-1:14:40\t-\tLMT\t1916 Jul 28",
+            ),
+            (
+                "-0:25:21 1 IST 1916 O 1 2s",
+                "\t\t\t-0:25:21\t1\tIST\t1916 Oct 1 2s",
+            ),
+            (
+                "L America/New_York US/Eastern",
+                "Link\tAmerica/New_York\tUS/Eastern",
+            ),
+            (
+                "Leap 2016 Dec 31 23:59:60 + S",
+                "Leap\t2016\tDec\t31\t23:59:60\t+\tS",
+            ),
+            ("# comment", ""),
+        ] {
+            let line = Line::new(input).unwrap();
+            assert_eq!(line.to_string(), output);
+            assert_eq!(Line::new(output), Ok(line));
+        }
+    }
+
     #[test]
     fn negative_offsets_3() {
         static LINE: &str = "Zone America/Danmarkshavn -1:14:40 -    LMT 1916 Jul 28";
@@ -0,0 +1,411 @@
+//! Describing the rules that keep recurring after the last transition.
+//!
+//! A `FixedTimespanSet` only covers a fixed range of years, so it can’t say
+//! anything about instants after its last transition. Most zones that still
+//! observe daylight-saving time do so according to a pair of rules that
+//! recur every year (such as the United States’ “second Sunday in March” and
+//! “first Sunday in November”), so these can be kept in the form used by the
+//! POSIX `TZ` environment variable and by the footer of compiled TZif files:
+//!
+//! ```text
+//! EST5EDT,M3.2.0,M11.1.0
+//! ```
+//!
+//! The conversion from rule lines to POSIX rules follows the `stringrule`
+//! and `stringzone` functions in `zic.c`.
+//!
+//! ## Example
+//!
+//! ```
+//! use parse_zoneinfo::line::Line;
+//! use parse_zoneinfo::table::TableBuilder;
+//! use parse_zoneinfo::transitions::TableTransitions;
+//!
+//! let mut builder = TableBuilder::new();
+//! for line in [
+//!     "Rule  US  2007  max  -  Mar  Sun>=8  2:00  1:00  D",
+//!     "Rule  US  2007  max  -  Nov  Sun>=1  2:00  0     S",
+//!     "Zone  America/New_York  -5:00  US  E%sT",
+//! ] {
+//!     builder.add_line(Line::new(line)?).unwrap();
+//! }
+//!
+//! let table = builder.build();
+//! let posix = table.posix_tz("America/New_York").unwrap();
+//! assert_eq!(posix.to_string(), "EST5EDT,M3.2.0,M11.1.0");
+//! # Ok::<(), parse_zoneinfo::line::Error>(())
+//! ```
+
+use std::fmt;
+
+use crate::line::{DaySpec, Month, TimeType, Weekday, Year};
+use crate::table::{RuleInfo, Saving, ZoneInfo};
+use crate::transitions::{FixedTimespan, FixedTimespanSet};
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// The time zone in effect after the last transition of a zone, in the
+/// shape of a POSIX `TZ` string.
+#[derive(PartialEq, Debug, Clone)]
+pub struct PosixTz {
+    /// The timespan in effect whenever daylight-saving time isn’t.
+    pub std: FixedTimespan,
+
+    /// The daylight-saving timespan and the rules that switch to and from
+    /// it, or `None` if the zone stays on standard time.
+    pub dst: Option<PosixDst>,
+}
+
+/// The daylight-saving part of a `PosixTz`.
+#[derive(PartialEq, Debug, Clone)]
+pub struct PosixDst {
+    /// The timespan in effect between `start` and `end`.
+    pub timespan: FixedTimespan,
+
+    /// When the switch from standard time to this timespan happens.
+    pub start: PosixRule,
+
+    /// When the switch back to standard time happens.
+    pub end: PosixRule,
+}
+
+/// A yearly transition instant.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct PosixRule {
+    /// The day on which the transition happens.
+    pub day: PosixDay,
+
+    /// The local wall-clock time of the transition, as the number of seconds
+    /// past midnight at the start of `day`, measured in the offset in effect
+    /// just before it. This can be negative or greater than a day.
+    pub time: i64,
+}
+
+/// A day of the year in one of the three forms allowed by POSIX.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum PosixDay {
+    /// `Jn`: the day of the year, from 1 to 365, where February 29 is never
+    /// counted.
+    Julian(u16),
+
+    /// `n`: the zero-based day of the year, from 0 to 365, where February 29
+    /// is counted in leap years.
+    Zero(u16),
+
+    /// `Mm.w.d`: the given weekday of the given week of a month, where week 1
+    /// is the first week in which the weekday occurs and week 5 is the last.
+    MonthWeekday(Month, u8, Weekday),
+}
+
+const DAYS_BEFORE_MONTH: [u16; 12] = [0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334];
+
+impl PosixRule {
+    /// Converts the *ON* and *AT* columns of a rule into a POSIX rule,
+    /// returning `None` if it can’t be represented as one.
+    ///
+    /// `std_offset` is the standard offset of the zone, and `save` is the
+    /// amount of daylight-saving time in effect just before the rule fires.
+    fn from_rule(rule: &RuleInfo, std_offset: i64, save: i64) -> Option<PosixRule> {
+        let mut time = rule.time;
+        match rule.time_type {
+            TimeType::UTC => time += std_offset + save,
+            TimeType::Standard => time += save,
+            TimeType::Wall => {}
+        }
+
+        let month = rule.month;
+        let day = match rule.day {
+            DaySpec::Ordinal(29) if month == Month::February => return None,
+            DaySpec::Ordinal(day) => {
+                let total = DAYS_BEFORE_MONTH[month as usize - 1];
+                // Omit the “J” in January and February, as that’s shorter.
+                if month as u8 <= 2 {
+                    PosixDay::Zero(total + day as u16 - 1)
+                } else {
+                    PosixDay::Julian(total + day as u16)
+                }
+            }
+            DaySpec::Last(weekday) => PosixDay::MonthWeekday(month, 5, weekday),
+            DaySpec::FirstOnOrAfter(weekday, day) => {
+                let offset = (day as i64 - 1) % 7;
+                time += offset * SECONDS_PER_DAY;
+                let week = 1 + (day as u8 - 1) / 7;
+                PosixDay::MonthWeekday(month, week, shift_back(weekday, offset))
+            }
+            DaySpec::LastOnOrBefore(weekday, day) if day == month.length(true) => {
+                PosixDay::MonthWeekday(month, 5, weekday)
+            }
+            DaySpec::LastOnOrBefore(weekday, day) => {
+                let offset = day as i64 % 7;
+                time += offset * SECONDS_PER_DAY;
+                let week = day as u8 / 7;
+                if week == 0 {
+                    return None;
+                }
+                PosixDay::MonthWeekday(month, week, shift_back(weekday, offset))
+            }
+        };
+
+        Some(PosixRule { day, time })
+    }
+}
+
+/// The weekday that is `days` days before the given one.
+fn shift_back(weekday: Weekday, days: i64) -> Weekday {
+    const WEEKDAYS: [Weekday; 7] = [
+        Weekday::Sunday,
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+    ];
+
+    WEEKDAYS[(weekday as i64 - days).rem_euclid(7) as usize]
+}
+
+impl PosixTz {
+    /// Computes the recurring rules for the last line of a zoneset, given
+    /// the rules it refers to (if any) and the timespans already computed
+    /// for it.
+    ///
+    /// Returns `None` if the rules can’t be represented as a POSIX `TZ`
+    /// string, such as when more than two of them recur forever.
+    pub(crate) fn new(
+        zone_info: &ZoneInfo,
+        rules: Option<&[RuleInfo]>,
+        timespans: &FixedTimespanSet,
+    ) -> Option<PosixTz> {
+        let last = match timespans.rest.last() {
+            Some((_, timespan)) => timespan,
+            None => &timespans.first,
+        };
+
+        let rules = match (&zone_info.saving, rules) {
+            (Saving::Multiple(_), Some(rules)) => rules,
+            _ => {
+                return Some(PosixTz {
+                    std: last.clone(),
+                    dst: None,
+                })
+            }
+        };
+
+        let recurring = rules
+            .iter()
+            .filter(|rule| rule.to_year == Some(Year::Maximum))
+            .collect::<Vec<_>>();
+
+        let (std_rule, dst_rule) = match recurring[..] {
+            [] => {
+                return Some(PosixTz {
+                    std: last.clone(),
+                    dst: None,
+                })
+            }
+            [a, b] if a.time_to_add == 0 && b.time_to_add != 0 => (a, b),
+            [a, b] if a.time_to_add != 0 && b.time_to_add == 0 => (b, a),
+            _ => return None,
+        };
+
+        let offset = zone_info.offset;
+        let save = dst_rule.time_to_add;
+        let std = FixedTimespan {
+            utc_offset: offset,
+            dst_offset: 0,
+            name: zone_info
+                .format
+                .format(offset, 0, std_rule.letters.as_ref()),
+        };
+        let dst = FixedTimespan {
+            utc_offset: offset,
+            dst_offset: save,
+            name: zone_info
+                .format
+                .format(offset, save, dst_rule.letters.as_ref()),
+        };
+
+        Some(PosixTz {
+            std,
+            dst: Some(PosixDst {
+                timespan: dst,
+                start: PosixRule::from_rule(dst_rule, offset, 0)?,
+                end: PosixRule::from_rule(std_rule, offset, save)?,
+            }),
+        })
+    }
+}
+
+impl fmt::Display for PosixTz {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_name(f, &self.std.name)?;
+        write_time(f, -self.std.total_offset())?;
+
+        if let Some(ref dst) = self.dst {
+            write_name(f, &dst.timespan.name)?;
+            // The offset can be left out if it’s an hour ahead of standard time.
+            if dst.timespan.total_offset() != self.std.total_offset() + 60 * 60 {
+                write_time(f, -dst.timespan.total_offset())?;
+            }
+            write!(f, ",{},{}", dst.start, dst.end)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for PosixRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.day {
+            PosixDay::Julian(day) => write!(f, "J{day}")?,
+            PosixDay::Zero(day) => write!(f, "{day}")?,
+            PosixDay::MonthWeekday(month, week, weekday) => {
+                write!(f, "M{}.{week}.{}", month as u8, weekday as u8)?
+            }
+        }
+
+        // The time can be left out if it’s the default of 02:00.
+        if self.time != 2 * 60 * 60 {
+            f.write_str("/")?;
+            write_time(f, self.time)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes an abbreviation, quoting it in angle brackets unless it consists
+/// only of letters.
+fn write_name(f: &mut fmt::Formatter, name: &str) -> fmt::Result {
+    if name.chars().all(|c| c.is_ascii_alphabetic()) {
+        f.write_str(name)
+    } else {
+        write!(f, "<{name}>")
+    }
+}
+
+/// Writes a number of seconds as `[-]h[:mm[:ss]]`.
+fn write_time(f: &mut fmt::Formatter, seconds: i64) -> fmt::Result {
+    if seconds < 0 {
+        f.write_str("-")?;
+    }
+
+    let seconds = seconds.abs();
+    write!(f, "{}", seconds / 3600)?;
+    if seconds % 3600 != 0 {
+        write!(f, ":{:02}", seconds / 60 % 60)?;
+        if seconds % 60 != 0 {
+            write!(f, ":{:02}", seconds % 60)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::line::Line;
+    use crate::table::TableBuilder;
+    use crate::transitions::TableTransitions;
+
+    fn posix_tz(lines: &[&str], zone_name: &str) -> Option<String> {
+        let mut builder = TableBuilder::new();
+        for line in lines {
+            builder.add_line(Line::new(line).unwrap()).unwrap();
+        }
+
+        let table = builder.build();
+        table.posix_tz(zone_name).map(|posix| posix.to_string())
+    }
+
+    #[test]
+    fn no_rules() {
+        let tz = posix_tz(&["Zone  Asia/Kolkata  5:30  -  IST"], "Asia/Kolkata");
+        assert_eq!(tz.as_deref(), Some("IST-5:30"));
+    }
+
+    #[test]
+    fn numeric_abbreviation() {
+        let tz = posix_tz(&["Zone  Asia/Tehran  3:30  -  %z"], "Asia/Tehran");
+        assert_eq!(tz.as_deref(), Some("<+0330>-3:30"));
+    }
+
+    #[test]
+    fn utc_times() {
+        let tz = posix_tz(
+            &[
+                "Rule  EU  1981  max  -  Mar  lastSun  1:00u  1:00  S",
+                "Rule  EU  1996  max  -  Oct  lastSun  1:00u  0     -",
+                "Zone  Europe/Berlin  1:00  EU  CE%sT",
+            ],
+            "Europe/Berlin",
+        );
+        assert_eq!(tz.as_deref(), Some("CET-1CEST,M3.5.0,M10.5.0/3"));
+    }
+
+    #[test]
+    fn southern_hemisphere() {
+        let tz = posix_tz(
+            &[
+                "Rule  AN  2008  max  -  Apr  Sun>=1  2:00s  0     S",
+                "Rule  AN  2008  max  -  Oct  Sun>=1  2:00s  1:00  D",
+                "Zone  Australia/Sydney  10:00  AN  AE%sT",
+            ],
+            "Australia/Sydney",
+        );
+        assert_eq!(tz.as_deref(), Some("AEST-10AEDT,M10.1.0,M4.1.0/3"));
+    }
+
+    #[test]
+    fn shifted_weekday() {
+        let tz = posix_tz(
+            &[
+                "Rule  Zion  2013  max  -  Mar  Fri>=23  2:00  1:00  D",
+                "Rule  Zion  2013  max  -  Oct  lastSun  2:00  0     S",
+                "Zone  Asia/Jerusalem  2:00  Zion  I%sT",
+            ],
+            "Asia/Jerusalem",
+        );
+        assert_eq!(tz.as_deref(), Some("IST-2IDT,M3.4.4/26,M10.5.0"));
+    }
+
+    #[test]
+    fn last_weekday_on_or_before() {
+        let tz = posix_tz(
+            &[
+                "Rule  Palestine  2072  max  -  Mar  Sat<=30  2:00  1:00  S",
+                "Rule  Palestine  2072  max  -  Oct  Sat<=30  2:00  0     -",
+                "Zone  Asia/Gaza  2:00  Palestine  EE%sT",
+            ],
+            "Asia/Gaza",
+        );
+        assert_eq!(tz.as_deref(), Some("EET-2EEST,M3.4.4/50,M10.4.4/50"));
+    }
+
+    #[test]
+    fn negative_dst() {
+        let tz = posix_tz(
+            &[
+                "Rule  Eire  1981  max  -  Mar  lastSun  1:00u  0      -",
+                "Rule  Eire  1996  max  -  Oct  lastSun  1:00u  -1:00  -",
+                "Zone  Europe/Dublin  1:00  Eire  IST/GMT",
+            ],
+            "Europe/Dublin",
+        );
+        assert_eq!(tz.as_deref(), Some("IST-1GMT0,M10.5.0,M3.5.0/1"));
+    }
+
+    #[test]
+    fn double_hour_saving() {
+        let tz = posix_tz(
+            &[
+                "Rule  Troll  2005  max  -  Mar  lastSun  1:00u  2:00  +02",
+                "Rule  Troll  2004  max  -  Oct  lastSun  1:00u  0:00  +00",
+                "Zone  Antarctica/Troll  0  Troll  %s",
+            ],
+            "Antarctica/Troll",
+        );
+        assert_eq!(tz.as_deref(), Some("<+00>0<+02>-2,M3.5.0/1,M10.5.0/3"));
+    }
+}
@@ -0,0 +1,276 @@
+//! Writing a Table back out as tz source text.
+//!
+//! This is the reverse of what the `line` and `table` modules do: it turns a
+//! table, which may have been built or edited in Rust, into text that zic
+//! and the rest of the tz tooling can read. The rules of each ruleset, the
+//! lines of each zone, and the links each get their columns aligned, much
+//! like the hand-written source files, with rulesets, zones and links sorted
+//! by name.
+//!
+//! Only the rules, zones and links are written, along with the version of
+//! the data as a comment like the one `tzdata.zi` starts with. Leap seconds
+//! go in a file of their own, so they’re left out.
+//!
+//! ```
+//! use parse_zoneinfo::line::Line;
+//! use parse_zoneinfo::source::write_source;
+//! use parse_zoneinfo::table::TableBuilder;
+//!
+//! let mut builder = TableBuilder::new();
+//! for line in [
+//!     "Zone Pacific/Auckland 11:39:04 - LMT 1868 Nov 2",
+//!     "12:00 NZ NZ%sT",
+//!     "Rule NZ 1974 o - N Su>=1 2s 1 D",
+//!     "Link Pacific/Auckland Antarctica/McMurdo",
+//! ] {
+//!     builder.add_line(Line::new(line)?).unwrap();
+//! }
+//! let table = builder.build();
+//!
+//! let mut text = Vec::new();
+//! write_source(&mut text, &table).unwrap();
+//! assert_eq!(
+//!     String::from_utf8(text).unwrap(),
+//!     "Rule NZ 1974 only - Nov Sun>=1 2:00s 1:00 D\n\
+//!      \n\
+//!      Zone Pacific/Auckland 11:39:04 -  LMT   1868 Nov 2\n\
+//!      \x20                     12:00    NZ NZ%sT\n\
+//!      \n\
+//!      Link Pacific/Auckland Antarctica/McMurdo\n",
+//! );
+//! # Ok::<(), parse_zoneinfo::line::Error>(())
+//! ```
+
+use std::io::{self, Write};
+
+use crate::line::{TimeSpec, TimeSpecAndType};
+use crate::table::{RuleInfo, Saving, Table, ZoneInfo};
+
+/// Writes the rules, zones and links of a table as tz source text.
+pub fn write_source<W: Write>(out: &mut W, table: &Table) -> io::Result<()> {
+    let mut blocks = Vec::<Vec<Vec<String>>>::new();
+
+    let mut rulesets = table.rulesets.iter().collect::<Vec<_>>();
+    rulesets.sort_by_key(|&(name, _)| name);
+    for (name, rules) in rulesets {
+        blocks.push(rules.iter().map(|rule| rule_row(name, rule)).collect());
+    }
+
+    let mut zonesets = table.zonesets.iter().collect::<Vec<_>>();
+    zonesets.sort_by_key(|&(name, _)| name);
+    for (name, zoneset) in zonesets {
+        let rows = zoneset.iter().enumerate().map(|(i, info)| {
+            // Continuation lines are indented to line up with the first.
+            let (keyword, name) = match i {
+                0 => ("Zone", name.as_str()),
+                _ => ("", ""),
+            };
+            let mut row = vec![keyword.to_owned(), name.to_owned()];
+            row.extend(zone_info_columns(info));
+            row
+        });
+        blocks.push(rows.collect());
+    }
+
+    let mut links = table.links.iter().collect::<Vec<_>>();
+    links.sort_by_key(|&(new, _)| new);
+    if !links.is_empty() {
+        let rows = links
+            .into_iter()
+            .map(|(new, existing)| vec!["Link".to_owned(), existing.clone(), new.clone()]);
+        blocks.push(rows.collect());
+    }
+
+    if let Some(version) = &table.version {
+        writeln!(out, "# version {version}")?;
+        if !blocks.is_empty() {
+            writeln!(out)?;
+        }
+    }
+    for (i, block) in blocks.iter().enumerate() {
+        if i > 0 {
+            writeln!(out)?;
+        }
+        write_aligned(out, block)?;
+    }
+    Ok(())
+}
+
+/// Returns the columns of a rule line.
+fn rule_row(name: &str, rule: &RuleInfo) -> Vec<String> {
+    let to_year = match rule.to_year {
+        Some(year) => year.to_string(),
+        None => "only".to_owned(),
+    };
+    vec![
+        "Rule".to_owned(),
+        name.to_owned(),
+        rule.from_year.to_string(),
+        to_year,
+        "-".to_owned(),
+        rule.month.to_string(),
+        rule.day.to_string(),
+        TimeSpecAndType(time_spec(rule.time), rule.time_type).to_string(),
+        amount(rule.time_to_add),
+        rule.letters.as_deref().unwrap_or("-").to_owned(),
+    ]
+}
+
+/// Returns the `STDOFF`, `RULES`, `FORMAT` and `UNTIL` columns of a line of
+/// a zone, leaving out the last if it doesn’t end.
+fn zone_info_columns(info: &ZoneInfo) -> Vec<String> {
+    let saving = match info.saving {
+        Saving::NoSaving => "-".to_owned(),
+        Saving::OneOff(amount) => time_spec(amount).to_string(),
+        Saving::Multiple(ref name) => name.clone(),
+    };
+    let mut columns = vec![amount(info.offset), saving, info.format.to_string()];
+    columns.extend(info.end_time.map(|time| time.to_string()));
+    columns
+}
+
+/// Returns an amount of time as the source files write offsets and saved
+/// time, which is `0` rather than `0:00` when there’s none.
+fn amount(seconds: i64) -> String {
+    match seconds {
+        0 => "0".to_owned(),
+        seconds => time_spec(seconds).to_string(),
+    }
+}
+
+/// Turns a number of seconds back into the hours and minutes, and seconds
+/// if there are any, they were written as.
+fn time_spec(seconds: i64) -> TimeSpec {
+    let sign = seconds.signum().max(-1) as i8;
+    let seconds = seconds.abs();
+    let (hours, minutes, seconds) = (
+        (seconds / 3600) as i8,
+        (seconds / 60 % 60) as i8,
+        (seconds % 60) as i8,
+    );
+    match seconds {
+        // Every component of a negative time is negative.
+        0 => TimeSpec::HoursMinutes(sign * hours, sign * minutes),
+        _ => TimeSpec::HoursMinutesSeconds(sign * hours, sign * minutes, sign * seconds),
+    }
+}
+
+/// Writes rows of columns, padding each column to the width of its widest
+/// value but the last.
+fn write_aligned<W: Write>(out: &mut W, rows: &[Vec<String>]) -> io::Result<()> {
+    let mut widths = Vec::new();
+    for row in rows {
+        widths.resize(widths.len().max(row.len()), 0);
+        for (width, column) in widths.iter_mut().zip(row) {
+            *width = (*width).max(column.chars().count());
+        }
+    }
+
+    for row in rows {
+        let mut line = String::new();
+        for (i, (column, width)) in row.iter().zip(&widths).enumerate() {
+            if i > 0 {
+                line.push(' ');
+            }
+            line.push_str(column);
+            if i + 1 < row.len() {
+                line.extend(std::iter::repeat(' ').take(width - column.chars().count()));
+            }
+        }
+        writeln!(out, "{}", line.trim_end())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::line::Line;
+    use crate::table::TableBuilder;
+
+    fn round_trip(lines: &[&str]) -> String {
+        let mut builder = TableBuilder::new();
+        for line in lines {
+            builder.add_line(Line::new(line).unwrap()).unwrap();
+        }
+        let table = builder.build();
+
+        let mut text = Vec::new();
+        write_source(&mut text, &table).unwrap();
+        let text = String::from_utf8(text).unwrap();
+
+        let mut builder = TableBuilder::new();
+        builder.add_file("written", text.as_bytes()).unwrap();
+        assert_eq!(builder.build(), table);
+        text
+    }
+
+    #[test]
+    fn aligned() {
+        let text = round_trip(&[
+            "Rule  Chicago  1920  only  -  Jun  13  2:00  1:00  D",
+            "Rule  Chicago  1920  1921  -  Oct  lastSun  2:00  0  S",
+            "Rule  Chicago  1921  only  -  Mar  lastSun  2:00  1:00  D",
+            "Zone America/Chicago  -5:50:36 -  LMT  1883 Nov 18 18:00u",
+            "                      -6:00  Chicago  C%sT  1936 Mar  1  2:00",
+            "                      -5:00  -  EST",
+        ]);
+        assert_eq!(
+            text,
+            "\
+Rule Chicago 1920 only - Jun 13      2:00 1:00 D
+Rule Chicago 1920 1921 - Oct lastSun 2:00 0    S
+Rule Chicago 1921 only - Mar lastSun 2:00 1:00 D
+
+Zone America/Chicago -5:50:36 -       LMT  1883 Nov 18 18:00u
+                     -6:00    Chicago C%sT 1936 Mar 1 2:00
+                     -5:00    -       EST
+"
+        );
+    }
+
+    #[test]
+    fn times() {
+        assert_eq!(time_spec(0), TimeSpec::HoursMinutes(0, 0));
+        assert_eq!(time_spec(-1800), TimeSpec::HoursMinutes(0, -30));
+        assert_eq!(
+            time_spec(-20_962),
+            TimeSpec::HoursMinutesSeconds(-5, -49, -22)
+        );
+        assert_eq!(time_spec(-1800).to_string(), "-0:30");
+        assert_eq!(amount(0), "0");
+        assert_eq!(amount(3600), "1:00");
+    }
+
+    #[test]
+    fn version_and_savings() {
+        let mut builder = TableBuilder::new();
+        for line in [
+            "Zone  Europe/Dublin  -0:25:21  -  DMT  1916 Oct  1  2:00s",
+            "                     -0:25:21  1:00  IST  1916 Oct  1  2:00s",
+            "                     1:00  -1:00  GMT/IST",
+            "Zone  Etc/UTC  0  -  UTC",
+        ] {
+            builder.add_line(Line::new(line).unwrap()).unwrap();
+        }
+        let mut table = builder.build();
+        table.version = Some("2025b".to_owned());
+
+        let mut text = Vec::new();
+        write_source(&mut text, &table).unwrap();
+        let text = String::from_utf8(text).unwrap();
+        assert_eq!(
+            text,
+            "\
+# version 2025b
+
+Zone Etc/UTC 0 - UTC
+
+Zone Europe/Dublin -0:25:21 -     DMT     1916 Oct 1 2:00s
+                   -0:25:21 1:00  IST     1916 Oct 1 2:00s
+                   1:00     -1:00 GMT/IST
+"
+        );
+        assert_eq!(Table::from_tzdata_zi(text.as_bytes()).unwrap(), table);
+    }
+}
@@ -0,0 +1,307 @@
+//! Parsing the tables that tie zones to the countries that use them.
+//!
+//! Next to the files with zone definitions, the tz database comes with three
+//! tab-separated tables:
+//!
+//! - `iso3166.tab` lists the ISO 3166 code and name of each country;
+//! - `zone1970.tab` lists the zones whose clocks have differed since 1970,
+//!   each with every country it covers;
+//! - `zone.tab` is the older form of that table, which has one line per
+//!   country and zone, and so also lists zones that are links nowadays.
+//!
+//! Lines of the zone tables are parsed into `ZoneTabLine`s, and lines of
+//! `iso3166.tab` into `CountryLine`s. Both parsers return `None` for
+//! comments and blank lines.
+//!
+//! ```
+//! use parse_zoneinfo::tab::{Coordinates, CountryLine, ZoneTabLine};
+//!
+//! let line = ZoneTabLine::new("AE,OM,RE,SC,TF\t+2518+05518\tAsia/Dubai\tCrozet");
+//! assert_eq!(line, Ok(Some(ZoneTabLine {
+//!     countries:   vec!["AE", "OM", "RE", "SC", "TF"],
+//!     coordinates: Coordinates { latitude: 25 * 3600 + 18 * 60, longitude: 55 * 3600 + 18 * 60 },
+//!     zone:        "Asia/Dubai",
+//!     comments:    Some("Crozet"),
+//! })));
+//!
+//! let line = CountryLine::new("BR\tBrazil");
+//! assert_eq!(line, Ok(Some(CountryLine { code: "BR", name: "Brazil" })));
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum Error {
+    InvalidCountryCode(String),
+    InvalidCoordinates(String),
+    NotParsedAsZoneTabLine(String),
+    NotParsedAsCountryLine(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidCountryCode(s) => write!(f, "invalid country code: \"{}\"", s),
+            Error::InvalidCoordinates(s) => write!(f, "invalid coordinates: \"{}\"", s),
+            Error::NotParsedAsZoneTabLine(s) => {
+                write!(f, "failed to parse line as a zone table entry: \"{}\"", s)
+            }
+            Error::NotParsedAsCountryLine(s) => {
+                write!(f, "failed to parse line as a country: \"{}\"", s)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A line of `zone1970.tab` or `zone.tab`, which tells where a zone is used.
+#[derive(PartialEq, Debug, Clone)]
+pub struct ZoneTabLine<'a> {
+    /// The ISO 3166 codes of the countries that use this zone, of which
+    /// `zone.tab` only ever lists one.
+    pub countries: Vec<&'a str>,
+
+    /// The latitude and longitude of the zone's principal location.
+    pub coordinates: Coordinates,
+
+    /// The name of the zone.
+    pub zone: &'a str,
+
+    /// The region of the country the zone covers, if the country has more
+    /// than one zone.
+    pub comments: Option<&'a str>,
+}
+
+impl<'a> ZoneTabLine<'a> {
+    /// Attempt to parse this line, returning `None` if it is a comment or
+    /// blank, or an `Error` if it couldn't be parsed.
+    pub fn new(input: &'a str) -> Result<Option<ZoneTabLine<'a>>, Error> {
+        if is_space(input) {
+            return Ok(None);
+        }
+
+        let mut columns = input.split('\t');
+        let (countries, coordinates, zone) = match (columns.next(), columns.next(), columns.next())
+        {
+            (Some(countries), Some(coordinates), Some(zone)) if !zone.is_empty() => {
+                (countries, coordinates, zone)
+            }
+            _ => return Err(Error::NotParsedAsZoneTabLine(input.to_owned())),
+        };
+        let comments = columns.next().filter(|comments| !comments.is_empty());
+        if columns.next().is_some() {
+            return Err(Error::NotParsedAsZoneTabLine(input.to_owned()));
+        }
+
+        Ok(Some(ZoneTabLine {
+            countries: countries
+                .split(',')
+                .map(country_code)
+                .collect::<Result<_, _>>()?,
+            coordinates: Coordinates::from_str(coordinates)?,
+            zone,
+            comments,
+        }))
+    }
+}
+
+/// A point on Earth, in seconds of arc north of the equator and east of the
+/// prime meridian.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct Coordinates {
+    pub latitude: i32,
+    pub longitude: i32,
+}
+
+impl FromStr for Coordinates {
+    type Err = Error;
+
+    /// Parses coordinates in the ISO 6709 sign-degrees-minutes-seconds
+    /// format the tables use, such as `+4230+00131` or `+404251-0740023`,
+    /// where the seconds are optional.
+    fn from_str(input: &str) -> Result<Coordinates, Error> {
+        let invalid = || Error::InvalidCoordinates(input.to_owned());
+        if !input.is_ascii() {
+            return Err(invalid());
+        }
+        let split = input.get(1..).and_then(|rest| rest.find(['+', '-']));
+        let split = split.ok_or_else(invalid)? + 1;
+        let (latitude, longitude) = input.split_at(split);
+        Ok(Coordinates {
+            latitude: angle(latitude, 2, 90).ok_or_else(invalid)?,
+            longitude: angle(longitude, 3, 180).ok_or_else(invalid)?,
+        })
+    }
+}
+
+/// Parses a signed angle with the given number of digits for its degrees,
+/// followed by minutes and optionally seconds.
+fn angle(input: &str, degree_digits: usize, max: i32) -> Option<i32> {
+    // Only ASCII input makes it in here.
+    let (sign, digits) = match input.split_at(1) {
+        ("+", digits) => (1, digits),
+        ("-", digits) => (-1, digits),
+        _ => return None,
+    };
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let number = |range: std::ops::Range<usize>| digits[range].parse::<i32>().ok();
+    let (degrees, minutes, seconds) = match digits.len().checked_sub(degree_digits)? {
+        2 => (
+            number(0..degree_digits)?,
+            number(degree_digits..degree_digits + 2)?,
+            0,
+        ),
+        4 => (
+            number(0..degree_digits)?,
+            number(degree_digits..degree_digits + 2)?,
+            number(degree_digits + 2..degree_digits + 4)?,
+        ),
+        _ => return None,
+    };
+
+    let angle = degrees * 3600 + minutes * 60 + seconds;
+    match minutes < 60 && seconds < 60 && angle <= max * 3600 {
+        true => Some(sign * angle),
+        false => None,
+    }
+}
+
+/// A line of `iso3166.tab`, which names a country.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct CountryLine<'a> {
+    /// The two-letter ISO 3166 code of the country.
+    pub code: &'a str,
+
+    /// The usual English name of the country.
+    pub name: &'a str,
+}
+
+impl<'a> CountryLine<'a> {
+    /// Attempt to parse this line, returning `None` if it is a comment or
+    /// blank, or an `Error` if it couldn't be parsed.
+    pub fn new(input: &'a str) -> Result<Option<CountryLine<'a>>, Error> {
+        if is_space(input) {
+            return Ok(None);
+        }
+
+        match input.split_once('\t') {
+            Some((code, name)) if !name.is_empty() && !name.contains('\t') => {
+                Ok(Some(CountryLine {
+                    code: country_code(code)?,
+                    name,
+                }))
+            }
+            _ => Err(Error::NotParsedAsCountryLine(input.to_owned())),
+        }
+    }
+}
+
+fn is_space(input: &str) -> bool {
+    input.starts_with('#') || input.trim().is_empty()
+}
+
+fn country_code(code: &str) -> Result<&str, Error> {
+    match code.len() == 2 && code.bytes().all(|b| b.is_ascii_uppercase()) {
+        true => Ok(code),
+        false => Err(Error::InvalidCountryCode(code.to_owned())),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn zone_tab() {
+        assert_eq!(
+            ZoneTabLine::new("BR\t-2332-04637\tAmerica/Sao_Paulo\tBrazil (southeast: GO, DF, MG, ES, RJ, SP, PR, SC, RS)"),
+            Ok(Some(ZoneTabLine {
+                countries: vec!["BR"],
+                coordinates: Coordinates {
+                    latitude: -(23 * 3600 + 32 * 60),
+                    longitude: -(46 * 3600 + 37 * 60),
+                },
+                zone: "America/Sao_Paulo",
+                comments: Some("Brazil (southeast: GO, DF, MG, ES, RJ, SP, PR, SC, RS)"),
+            }))
+        );
+        assert_eq!(
+            ZoneTabLine::new("AD\t+4230+00131\tEurope/Andorra"),
+            Ok(Some(ZoneTabLine {
+                countries: vec!["AD"],
+                coordinates: Coordinates {
+                    latitude: 42 * 3600 + 30 * 60,
+                    longitude: 3600 + 31 * 60,
+                },
+                zone: "Europe/Andorra",
+                comments: None,
+            }))
+        );
+    }
+
+    #[test]
+    fn coordinates() {
+        assert_eq!(
+            "+404251-0740023".parse(),
+            Ok(Coordinates {
+                latitude: 40 * 3600 + 42 * 60 + 51,
+                longitude: -(74 * 3600 + 23),
+            })
+        );
+        assert_eq!(
+            "-9000+18000".parse(),
+            Ok(Coordinates {
+                latitude: -90 * 3600,
+                longitude: 180 * 3600,
+            })
+        );
+        for invalid in [
+            "",
+            "+4230",
+            "4230+00131",
+            "+4230+0131",
+            "+4260+00131",
+            "+9100+00000",
+        ] {
+            assert_eq!(
+                invalid.parse::<Coordinates>(),
+                Err(Error::InvalidCoordinates(invalid.to_owned()))
+            );
+        }
+    }
+
+    #[test]
+    fn space() {
+        assert_eq!(ZoneTabLine::new("# tzdb timezone descriptions"), Ok(None));
+        assert_eq!(ZoneTabLine::new("#@CY,TR\tAsia/,Europe/"), Ok(None));
+        assert_eq!(ZoneTabLine::new(""), Ok(None));
+        assert_eq!(CountryLine::new("#country-"), Ok(None));
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            ZoneTabLine::new("AD +4230+00131 Europe/Andorra"),
+            Err(Error::NotParsedAsZoneTabLine(
+                "AD +4230+00131 Europe/Andorra".to_owned()
+            ))
+        );
+        assert_eq!(
+            ZoneTabLine::new("AE,om\t+2518+05518\tAsia/Dubai"),
+            Err(Error::InvalidCountryCode("om".to_owned()))
+        );
+        assert_eq!(
+            CountryLine::new("BRA\tBrazil"),
+            Err(Error::InvalidCountryCode("BRA".to_owned()))
+        );
+        assert_eq!(
+            CountryLine::new("BR"),
+            Err(Error::NotParsedAsCountryLine("BR".to_owned()))
+        );
+    }
+}
@@ -36,14 +36,20 @@
 //! assert!(table.get_zoneset("UTC").is_none());
 //! # Ok::<(), parse_zoneinfo::line::Error>(())
 //! ```
+//!
+//! A whole release can also be read from its `tzdata.zi` file, which is how
+//! many systems ship it, with `Table::from_tzdata_zi`.
 
 use std::collections::hash_map::{Entry, HashMap};
 use std::fmt::{self, Write};
+use std::io::{self, BufRead};
+use std::ops::Range;
 
 use crate::line::{self, ChangeTime, DaySpec, Line, Month, TimeType, Year};
 
 /// A **table** of all the data in one or more zoneinfo files.
 #[derive(PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Table {
     /// Mapping of ruleset names to rulesets.
     pub rulesets: HashMap<String, Vec<RuleInfo>>,
@@ -53,9 +59,58 @@ pub struct Table {
 
     /// Mapping of link timezone names, to the names they link to.
     pub links: HashMap<String, String>,
+
+    /// The leap seconds, in the order they occurred.
+    pub leap_seconds: Vec<LeapSecond>,
+
+    /// The number of seconds since the Unix epoch at which the list of leap
+    /// seconds expires, if given.
+    pub leap_seconds_expiry: Option<i64>,
+
+    /// The version of the tz database the table was read from, if known.
+    pub version: Option<String>,
 }
 
 impl Table {
+    /// Reads a table from a `tzdata.zi` file, which holds every zone, rule
+    /// and link of a release in one file, in the compact form `zic` accepts.
+    ///
+    /// The version of the release is read from the `# version` comment at
+    /// the top of the file.
+    ///
+    /// ```
+    /// use parse_zoneinfo::table::Table;
+    ///
+    /// let zi = "\
+    /// ## version 2025b
+    /// R E 1981 ma - Mar lastSu 1u 1 S
+    /// R E 1996 ma - O lastSu 1u 0 -
+    /// Z Europe/Paris 0:9:21 - LMT 1891 Mar 16
+    /// 1 E CE%sT
+    /// L Europe/Paris Europe/Monaco
+    /// ";
+    /// let table = Table::from_tzdata_zi(zi.as_bytes()).unwrap();
+    /// assert_eq!(table.version.as_deref(), Some("2025b"));
+    /// assert_eq!(table.get_zoneset("Europe/Monaco").unwrap().len(), 2);
+    /// ```
+    pub fn from_tzdata_zi(mut reader: impl BufRead) -> Result<Table, ReadError> {
+        const NAME: &str = "tzdata.zi";
+        let mut text = String::new();
+        reader
+            .read_to_string(&mut text)
+            .map_err(|e| ReadError::Io(NAME.to_owned(), e))?;
+
+        let mut builder = TableBuilder::new();
+        builder.add_file(NAME, text.as_bytes())?;
+        let mut table = builder.build();
+        table.version = text
+            .lines()
+            .filter_map(|line| line.strip_prefix('#'))
+            .find_map(|comment| comment.trim_start().strip_prefix("version"))
+            .map(|version| version.trim().to_owned());
+        Ok(table)
+    }
+
     /// Tries to find the zoneset with the given name by looking it up in
     /// either the zonesets map or the links map.
     pub fn get_zoneset(&self, zone_name: &str) -> Option<&[ZoneInfo]> {
@@ -75,7 +130,8 @@ impl Table {
 /// This mimics the `Rule` struct in the `line` module, only its uses owned
 /// Strings instead of string slices, and has had some pre-processing
 /// applied to it.
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RuleInfo {
     /// The year that this rule *starts* applying.
     pub from_year: Year,
@@ -145,6 +201,39 @@ impl RuleInfo {
     }
 }
 
+/// A leap second definition line.
+///
+/// This mimics the `Leap` struct in the `line` module, with its date and
+/// time turned into a timestamp.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LeapSecond {
+    /// The number of seconds since the Unix epoch from which the difference
+    /// between UTC and TAI includes this leap second.
+    pub timestamp: i64,
+
+    /// The number of seconds this leap second adds to the difference
+    /// between UTC and TAI: 1 for an added second, or -1 for a removed one.
+    pub correction: i64,
+
+    /// Whether the leap second happens at the given time in each zone’s wall
+    /// clock time, rather than at the same instant everywhere.
+    pub rolling: bool,
+}
+
+impl From<line::Leap> for LeapSecond {
+    fn from(leap: line::Leap) -> LeapSecond {
+        LeapSecond {
+            timestamp: leap.timestamp(),
+            correction: match leap.correction {
+                line::LeapCorrection::Added => 1,
+                line::LeapCorrection::Removed => -1,
+            },
+            rolling: leap.rolling,
+        }
+    }
+}
+
 /// An owned zone definition line.
 ///
 /// This struct mimics the `ZoneInfo` struct in the `line` module, *not* the
@@ -153,6 +242,7 @@ impl RuleInfo {
 /// As with `RuleInfo`, this struct uses owned Strings rather than string
 /// slices.
 #[derive(PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ZoneInfo {
     /// The number of seconds that need to be added to UTC to get the
     /// standard time in this zone.
@@ -191,13 +281,15 @@ impl<'line> From<line::ZoneInfo<'line>> for ZoneInfo {
 ///
 /// This is the owned version of the `Saving` type in the `line` module.
 #[derive(PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Saving {
     /// Just stick to the base offset.
     NoSaving,
 
     /// This amount of time should be saved while this timespan is in effect.
     /// (This is the equivalent to there being a single one-off rule with the
-    /// given amount of time to save).
+    /// given amount of time to save). A negative amount moves the clocks
+    /// back from the base offset, which still counts as DST.
     OneOff(i64),
 
     /// All rules with the given name should apply while this timespan is in
@@ -208,6 +300,7 @@ pub enum Saving {
 /// The format string to generate a time zone abbreviation from.
 #[non_exhaustive]
 #[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Format {
     /// A constant format, which remains the same throughout both standard
     /// and DST timespans.
@@ -299,6 +392,18 @@ impl Format {
     }
 }
 
+impl fmt::Display for Format {
+    /// Writes the template this format was made from, which `Format::new`
+    /// turns back into the same format.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Format::Constant(s) | Format::Placeholder(s) => f.write_str(s),
+            Format::Alternate { standard, dst } => write!(f, "{}/{}", standard, dst),
+            Format::Offset => f.write_str("%z"),
+        }
+    }
+}
+
 /// A builder for `Table` values based on various line definitions.
 #[derive(PartialEq, Debug)]
 pub struct TableBuilder {
@@ -326,12 +431,51 @@ impl TableBuilder {
         }
     }
 
+    /// Adds every line of a file, returning an error that says where in the
+    /// file it went wrong if one of them couldn’t be parsed or added.
+    ///
+    /// The name of the file is only used in these errors.
+    ///
+    /// ```
+    /// use parse_zoneinfo::table::TableBuilder;
+    ///
+    /// let europe = "Zone\tEurope/Paris\t0:09:21\t-\tLMT\t1891 Mar 16\n\
+    ///               Zone\tEurope/Paris\t0:09:21\t-\tPMT\n";
+    /// let error = TableBuilder::new().add_file("europe", europe.as_bytes()).unwrap_err();
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "error: zone line with name \"Europe/Paris\" that already exists
+    ///  --> europe:2:6
+    ///   |
+    /// 2 | Zone\tEurope/Paris\t0:09:21\t-\tPMT
+    ///   |     \t^^^^^^^^^^^^"
+    /// );
+    /// ```
+    pub fn add_file(&mut self, name: &str, reader: impl BufRead) -> Result<(), ReadError> {
+        for (index, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| ReadError::Io(name.to_owned(), e))?;
+            let location = |text: Option<&str>| Location {
+                file: name.to_owned(),
+                line: index + 1,
+                columns: span(&line, text),
+                text: line.clone(),
+            };
+
+            let parsed = Line::new(&line).map_err(|e| ReadError::Line(location(e.text()), e))?;
+            self.add_line(parsed)
+                .map_err(|e| ReadError::Table(location(e.text()), e.to_string()))?;
+        }
+        Ok(())
+    }
+
     pub fn add_line<'line>(&mut self, line: Line<'line>) -> Result<(), Error<'line>> {
         match line {
             Line::Zone(zone) => self.add_zone_line(zone),
             Line::Continuation(cont) => self.add_continuation_line(cont),
             Line::Rule(rule) => self.add_rule_line(rule),
             Line::Link(link) => self.add_link_line(link),
+            Line::Leap(leap) => self.add_leap_line(leap),
+            Line::Expires(expires) => self.add_expires_line(expires),
             Line::Space => Ok(()),
         }
     }
@@ -351,7 +495,7 @@ impl TableBuilder {
         }
 
         let zoneset = match self.table.zonesets.entry(zone_line.name.to_owned()) {
-            Entry::Occupied(_) => return Err(Error::DuplicateZone),
+            Entry::Occupied(_) => return Err(Error::DuplicateZone(zone_line.name)),
             Entry::Vacant(e) => e.insert(Vec::new()),
         };
 
@@ -411,6 +555,39 @@ impl TableBuilder {
         }
     }
 
+    /// Adds a new line describing a leap second.
+    ///
+    /// Returns an error if it doesn’t come after the leap seconds that were
+    /// already added.
+    pub fn add_leap_line<'line>(&mut self, leap_line: line::Leap) -> Result<(), Error<'line>> {
+        let leap_second = LeapSecond::from(leap_line);
+        if let Some(last) = self.table.leap_seconds.last() {
+            if last.timestamp >= leap_second.timestamp {
+                return Err(Error::LeapSecondOutOfOrder);
+            }
+        }
+
+        self.table.leap_seconds.push(leap_second);
+        self.current_zoneset_name = None;
+        Ok(())
+    }
+
+    /// Adds a new line giving the expiry of the leap second list.
+    ///
+    /// Returns an error if an expiry was already given.
+    pub fn add_expires_line<'line>(
+        &mut self,
+        expires_line: line::Expires,
+    ) -> Result<(), Error<'line>> {
+        if self.table.leap_seconds_expiry.is_some() {
+            return Err(Error::DuplicateExpires);
+        }
+
+        self.table.leap_seconds_expiry = Some(expires_line.timestamp());
+        self.current_zoneset_name = None;
+        Ok(())
+    }
+
     /// Returns the table after it’s finished being built.
     pub fn build(self) -> Table {
         self.table
@@ -431,7 +608,13 @@ pub enum Error<'line> {
     DuplicateLink(&'line str),
 
     /// A zone line was passed in, but there’s already a zone with that name.
-    DuplicateZone,
+    DuplicateZone(&'line str),
+
+    /// A leap line was passed in, but it doesn’t come after the previous one.
+    LeapSecondOutOfOrder,
+
+    /// An expires line was passed in, but there already was one.
+    DuplicateExpires,
 }
 
 impl<'line> fmt::Display for Error<'line> {
@@ -443,13 +626,257 @@ impl<'line> fmt::Display for Error<'line> {
                     "continuation line follows line that isn't a zone definition line"
                 )
             }
-            Error::UnknownRuleset(_) => {
-                write!(f, "zone definition refers to a ruleset that isn't defined")
+            Error::UnknownRuleset(name) => write!(
+                f,
+                "zone definition refers to the ruleset \"{}\", which isn't defined",
+                name
+            ),
+            Error::DuplicateLink(name) => {
+                write!(f, "link line with name \"{}\" that already exists", name)
+            }
+            Error::DuplicateZone(name) => {
+                write!(f, "zone line with name \"{}\" that already exists", name)
             }
-            Error::DuplicateLink(_) => write!(f, "link line with name that already exists"),
-            Error::DuplicateZone => write!(f, "zone line with name that already exists"),
+            Error::LeapSecondOutOfOrder => {
+                write!(f, "leap line that doesn't follow the previous one")
+            }
+            Error::DuplicateExpires => write!(f, "more than one expires line"),
         }
     }
 }
 
 impl<'line> std::error::Error for Error<'line> {}
+
+impl<'line> Error<'line> {
+    /// Returns the name the error is about, if any.
+    fn text(&self) -> Option<&'line str> {
+        match *self {
+            Error::UnknownRuleset(name)
+            | Error::DuplicateLink(name)
+            | Error::DuplicateZone(name) => Some(name),
+            Error::SurpriseContinuationLine
+            | Error::LeapSecondOutOfOrder
+            | Error::DuplicateExpires => None,
+        }
+    }
+}
+
+/// Something that can go wrong while reading a `Table` from a file.
+#[derive(Debug)]
+pub enum ReadError {
+    /// The file with the given name couldn’t be read.
+    Io(String, io::Error),
+
+    /// A line couldn’t be parsed.
+    Line(Location, line::Error),
+
+    /// A line couldn’t be added to the table, for the reason given by the
+    /// message.
+    Table(Location, String),
+}
+
+impl ReadError {
+    /// Returns where in the file the error is, unless it couldn’t be read.
+    pub fn location(&self) -> Option<&Location> {
+        match self {
+            ReadError::Io(..) => None,
+            ReadError::Line(location, _) | ReadError::Table(location, _) => Some(location),
+        }
+    }
+}
+
+/// Formats the error like a compiler diagnostic, with the line it’s on and
+/// the part of it that’s wrong underlined.
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadError::Io(file, e) => write!(f, "error: failed to read {}: {}", file, e),
+            ReadError::Line(location, e) => location.fmt_diagnostic(f, e),
+            ReadError::Table(location, e) => location.fmt_diagnostic(f, e),
+        }
+    }
+}
+
+impl std::error::Error for ReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReadError::Io(_, e) => Some(e),
+            ReadError::Line(_, e) => Some(e),
+            ReadError::Table(..) => None,
+        }
+    }
+}
+
+/// Where in a file a `ReadError` is.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Location {
+    /// The name of the file.
+    pub file: String,
+
+    /// The number of the line, starting from 1.
+    pub line: usize,
+
+    /// The range of bytes of the line that’s wrong.
+    pub columns: Range<usize>,
+
+    /// The whole line.
+    pub text: String,
+}
+
+impl Location {
+    /// Returns the part of the line that’s wrong.
+    pub fn offending_text(&self) -> &str {
+        &self.text[self.columns.clone()]
+    }
+
+    /// Returns the column the wrong part of the line starts at, counting
+    /// characters from 1.
+    pub fn column(&self) -> usize {
+        self.text[..self.columns.start].chars().count() + 1
+    }
+
+    fn fmt_diagnostic(&self, f: &mut fmt::Formatter, message: &dyn fmt::Display) -> fmt::Result {
+        let number = self.line.to_string();
+        let indent = " ".repeat(number.len());
+        // Tabs are kept so that the underline lines up however they’re shown.
+        let padding = self.text[..self.columns.start]
+            .chars()
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+        let underline = "^".repeat(self.offending_text().chars().count().max(1));
+
+        writeln!(f, "error: {}", message)?;
+        writeln!(f, "{}--> {}", indent, self)?;
+        writeln!(f, "{} |", indent)?;
+        writeln!(f, "{} | {}", number, self.text)?;
+        write!(f, "{} | {}{}", indent, padding, underline)
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column())
+    }
+}
+
+/// Returns the range of bytes of the field of a line that holds the given
+/// text, or of everything before its comment if there’s no such field.
+fn span(line: &str, text: Option<&str>) -> Range<usize> {
+    let content = line.split('#').next().unwrap_or_default();
+    let mut fields = Vec::new();
+    let mut start = None;
+    for (i, c) in content.char_indices() {
+        match (c.is_ascii_whitespace(), start) {
+            (true, Some(s)) => {
+                fields.push(s..i);
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        fields.push(s..content.len());
+    }
+
+    // Errors about times leave out the letter for the type of time.
+    let field = text.and_then(|text| {
+        let exact = fields
+            .iter()
+            .find(|f| line[(*f).clone()].eq_ignore_ascii_case(text));
+        exact.or_else(|| {
+            fields.iter().find(|f| {
+                line[(*f).clone()]
+                    .get(..text.len())
+                    .map_or(false, |prefix| prefix.eq_ignore_ascii_case(text))
+            })
+        })
+    });
+    match (field, fields.first(), fields.last()) {
+        (Some(field), ..) => field.clone(),
+        (None, Some(first), Some(last)) => first.start..last.end,
+        _ => 0..line.len(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tzdata_zi() {
+        let zi = "\
+# version 2025b
+# This zic input file is in the public domain.
+R d 1916 o - Jun 14 23s 1 S
+R d 1916 1919 - O Su>=1 23s 0 -
+Z Africa/Algiers 0:12:12 - LMT 1891 Mar 16
+0:9:21 - PMT 1911 Mar 11
+0 d WE%sT 1940 F 25 2
+L Africa/Algiers Africa/Alger
+";
+        let table = Table::from_tzdata_zi(zi.as_bytes()).unwrap();
+        assert_eq!(table.version.as_deref(), Some("2025b"));
+        assert_eq!(table.rulesets["d"].len(), 2);
+        assert_eq!(table.rulesets["d"][0].to_year, None);
+
+        let zoneset = &table.zonesets["Africa/Algiers"];
+        assert_eq!(zoneset.len(), 3);
+        assert_eq!(zoneset[1].offset, 9 * 60 + 21);
+        assert_eq!(zoneset[2].saving, Saving::Multiple("d".to_owned()));
+        assert_eq!(table.links["Africa/Alger"], "Africa/Algiers");
+    }
+
+    #[test]
+    fn tzdata_zi_errors() {
+        let zi = "Z Africa/Algiers 0:12:12 - LMT 1891 Mar 16\nZ Africa/Algiers 0 - UTC\n";
+        let error = Table::from_tzdata_zi(zi.as_bytes()).unwrap_err();
+        let location = error.location().unwrap();
+        assert!(matches!(error, ReadError::Table(..)));
+        assert_eq!((location.line, location.column()), (2, 3));
+        assert_eq!(location.offending_text(), "Africa/Algiers");
+
+        // `M` could be March or May.
+        let zi = "R d 1916 o - M 14 23s 1 S\n";
+        let error = Table::from_tzdata_zi(zi.as_bytes()).unwrap_err();
+        assert!(matches!(
+            error,
+            ReadError::Line(_, line::Error::FailedMonthParse(_))
+        ));
+        assert_eq!(error.location().unwrap().offending_text(), "M");
+    }
+
+    #[test]
+    fn diagnostics() {
+        let text = "Rule\tEU\t1977\t1980\t-\tApr\tSun>=1\t 1:00x\t1:00\tS\n";
+        let error = TableBuilder::new()
+            .add_file("europe", text.as_bytes())
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "error: invalid time: \"1:00x\"
+ --> europe:1:33
+  |
+1 | Rule\tEU\t1977\t1980\t-\tApr\tSun>=1\t 1:00x\t1:00\tS
+  |     \t  \t    \t    \t \t   \t      \t ^^^^^"
+        );
+
+        let text = "\n\n\n\n\n\n\n\n\nZone Test/Zone 0 - UTC\nZone Test/Zone 0 - UTC # again\n";
+        let error = TableBuilder::new()
+            .add_file("test", text.as_bytes())
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "error: zone line with name \"Test/Zone\" that already exists
+  --> test:11:6
+   |
+11 | Zone Test/Zone 0 - UTC # again
+   |      ^^^^^^^^^"
+        );
+
+        let error = TableBuilder::new()
+            .add_file("test", "  1:00 - CET\n".as_bytes())
+            .unwrap_err();
+        assert_eq!(error.location().unwrap().offending_text(), "1:00 - CET");
+    }
+}
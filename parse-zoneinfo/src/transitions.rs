@@ -16,7 +16,8 @@
 //! (currently 1800..2100), rather than having an iterator that produces
 //! timespans indefinitely. Not only do we need a complete set of timespans
 //! for sorting, but it is not necessarily advisable to rely on offset
-//! changes so far into the future!
+//! changes so far into the future! The rules that keep applying after the
+//! last of these years are available separately, as a `PosixTz`.
 //!
 //! ### Example
 //!
@@ -101,6 +102,9 @@
 //! The logic in this file is based off of `zic.c`, which comes with the
 //! zoneinfo files and is in the public domain.
 
+use std::ops::Range;
+
+use crate::posix::PosixTz;
 use crate::table::{RuleInfo, Saving, Table, ZoneInfo};
 
 /// A set of timespans, separated by the instances at which the timespans
@@ -109,6 +113,7 @@ use crate::table::{RuleInfo, Saving, Table, ZoneInfo};
 /// This mimics the `FixedTimespanSet` struct in `datetime::cal::zone`,
 /// except it uses owned `Vec`s instead of slices.
 #[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FixedTimespanSet {
     /// The first timespan, which is assumed to have been in effect up until
     /// the initial transition instant (if any). Each set has to have at
@@ -129,6 +134,7 @@ pub struct FixedTimespanSet {
 /// instead of “total offset” and “is DST” fields, it has separate UTC and
 /// DST fields. Also, the name is an owned `String` here instead of a slice.
 #[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FixedTimespan {
     /// The number of seconds offset from UTC during this timespan.
     pub utc_offset: i64,
@@ -147,11 +153,20 @@ impl FixedTimespan {
     }
 }
 
+/// The years that timespans are computed for.
+pub const YEARS: Range<i64> = 1800..2100;
+
 /// Trait to put the `timespans` method on Tables.
 pub trait TableTransitions {
     /// Computes a fixed timespan set for the timezone with the given name.
     /// Returns `None` if the table doesn’t contain a time zone with that name.
     fn timespans(&self, zone_name: &str) -> Option<FixedTimespanSet>;
+
+    /// Computes the rules that remain in effect after the last transition
+    /// of the timezone with the given name. Returns `None` if the table
+    /// doesn’t contain a time zone with that name, or if its rules can’t be
+    /// represented as a POSIX `TZ` string.
+    fn posix_tz(&self, zone_name: &str) -> Option<PosixTz>;
 }
 
 impl TableTransitions for Table {
@@ -233,6 +248,16 @@ impl TableTransitions for Table {
 
         Some(builder.build())
     }
+
+    fn posix_tz(&self, zone_name: &str) -> Option<PosixTz> {
+        let zone_info = self.get_zoneset(zone_name)?.last()?;
+        let rules = match zone_info.saving {
+            Saving::Multiple(ref rules) => Some(&self.rulesets[rules][..]),
+            _ => None,
+        };
+
+        PosixTz::new(zone_info, rules, &self.timespans(zone_name)?)
+    }
 }
 
 #[derive(Debug, Default)]
@@ -292,7 +317,7 @@ impl FixedTimespanSetBuilder {
     ) {
         use std::mem::replace;
 
-        for year in 1800..2100 {
+        for year in YEARS {
             if use_until && year > timespan.end_time.unwrap().year() {
                 break;
             }
@@ -378,7 +403,7 @@ impl FixedTimespanSetBuilder {
     }
 
     fn build(mut self) -> FixedTimespanSet {
-        self.rest.sort_by(|a, b| a.0.cmp(&b.0));
+        self.rest.sort_by_key(|a| a.0);
 
         let first = match self.first {
             Some(ft) => ft,
@@ -0,0 +1,268 @@
+//! Writing timespans out as compiled TZif files.
+//!
+//! The `transitions` module computes the same set of timespans that `zic`
+//! does, and the `posix` module the rules that keep recurring after them.
+//! This module serialises the two into the binary format described by
+//! [RFC 8536], which is what ends up in a `zoneinfo` directory such as
+//! `/usr/share/zoneinfo`.
+//!
+//! ```
+//! use parse_zoneinfo::line::Line;
+//! use parse_zoneinfo::table::TableBuilder;
+//! use parse_zoneinfo::transitions::TableTransitions;
+//! use parse_zoneinfo::tzif::write_tzif;
+//!
+//! let mut builder = TableBuilder::new();
+//! builder.add_line(Line::new("Zone Etc/UTC 0 - UTC").unwrap()).unwrap();
+//! let table = builder.build();
+//!
+//! let mut file = Vec::new();
+//! let timespans = table.timespans("Etc/UTC").unwrap();
+//! let footer = table.posix_tz("Etc/UTC");
+//! write_tzif(&mut file, &timespans, footer.as_ref()).unwrap();
+//! assert!(file.starts_with(b"TZif2"));
+//! assert!(file.ends_with(b"\nUTC0\n"));
+//! ```
+//!
+//! [RFC 8536]: https://datatracker.ietf.org/doc/html/rfc8536
+
+use std::io::{self, Write};
+
+use crate::posix::PosixTz;
+use crate::transitions::{FixedTimespan, FixedTimespanSet};
+
+/// Writes a set of timespans as a TZif file, followed by the POSIX `TZ`
+/// string footer that describes the offsets after its last transition.
+///
+/// Without a footer, the last timespan is taken to last forever.
+///
+/// The file is written as version 2, or as version 3 if the footer needs
+/// one of the extensions that version introduced. Just like `zic -b slim`
+/// does, the legacy data block with 32-bit transition times is left empty,
+/// as readers of version 2 files only look at the 64-bit one.
+pub fn write_tzif<W: Write>(
+    out: &mut W,
+    timespans: &FixedTimespanSet,
+    footer: Option<&PosixTz>,
+) -> io::Result<()> {
+    let mut types = LocalTimeTypes::default();
+    types.index_of(&timespans.first)?;
+    let mut transitions = Vec::with_capacity(timespans.rest.len());
+    for (time, timespan) in &timespans.rest {
+        transitions.push((*time, types.index_of(timespan)?));
+    }
+
+    let version = match footer.map_or(false, needs_version_3) {
+        true => b'3',
+        false => b'2',
+    };
+
+    // The version 1 header and data block: a single unused local time type.
+    write_header(out, version, [0, 0, 0, 0, 1, 1])?;
+    out.write_all(&[0; 6])?;
+    out.write_all(&[0])?;
+
+    let counts = [
+        0,
+        0,
+        0,
+        transitions.len(),
+        types.records.len(),
+        types.abbreviations.len(),
+    ];
+    write_header(out, version, counts)?;
+    for (time, _) in &transitions {
+        out.write_all(&time.to_be_bytes())?;
+    }
+    for (_, index) in &transitions {
+        out.write_all(&[*index])?;
+    }
+    for (utc_offset, is_dst, abbreviation) in &types.records {
+        out.write_all(&utc_offset.to_be_bytes())?;
+        out.write_all(&[*is_dst as u8, *abbreviation])?;
+    }
+    out.write_all(&types.abbreviations)?;
+
+    match footer {
+        Some(footer) => writeln!(out, "\n{footer}"),
+        None => out.write_all(b"\n\n"),
+    }
+}
+
+/// Writes a header with the given version and the six counts in the order
+/// they appear in the file: UT/local indicators, standard/wall indicators,
+/// leap second records, transition times, local time types, and
+/// characters of abbreviations.
+fn write_header<W: Write>(out: &mut W, version: u8, counts: [usize; 6]) -> io::Result<()> {
+    out.write_all(b"TZif")?;
+    out.write_all(&[version])?;
+    out.write_all(&[0; 15])?;
+    for count in counts {
+        let count = u32::try_from(count).map_err(|_| invalid("too many records"))?;
+        out.write_all(&count.to_be_bytes())?;
+    }
+    Ok(())
+}
+
+/// Whether a footer uses the extensions of version 3, which allows
+/// transition times outside of the 0 to 24 hour range.
+fn needs_version_3(footer: &PosixTz) -> bool {
+    match &footer.dst {
+        Some(dst) => [dst.start.time, dst.end.time]
+            .iter()
+            .any(|time| !(0..=24 * 60 * 60).contains(time)),
+        None => false,
+    }
+}
+
+/// The distinct local time types of a zone, in order of first use.
+#[derive(Default)]
+struct LocalTimeTypes {
+    /// The total offset, DST flag, and index of the abbreviation.
+    records: Vec<(i32, bool, u8)>,
+    /// The NUL-terminated abbreviations.
+    abbreviations: Vec<u8>,
+}
+
+impl LocalTimeTypes {
+    /// Returns the index of the local time type for the given timespan,
+    /// adding it if it hasn’t been seen yet.
+    fn index_of(&mut self, timespan: &FixedTimespan) -> io::Result<u8> {
+        let utc_offset = i32::try_from(timespan.total_offset())
+            .map_err(|_| invalid("UTC offset out of range"))?;
+        let is_dst = timespan.dst_offset != 0;
+        let abbreviation = self.abbreviation(&timespan.name)?;
+
+        let record = (utc_offset, is_dst, abbreviation);
+        let index = match self.records.iter().position(|r| *r == record) {
+            Some(index) => index,
+            None => {
+                self.records.push(record);
+                self.records.len() - 1
+            }
+        };
+        u8::try_from(index).map_err(|_| invalid("too many local time types"))
+    }
+
+    fn abbreviation(&mut self, name: &str) -> io::Result<u8> {
+        let mut start = 0;
+        for existing in self.abbreviations.split(|&b| b == 0) {
+            if existing == name.as_bytes() && start < self.abbreviations.len() {
+                return u8::try_from(start).map_err(|_| invalid("abbreviations too long"));
+            }
+            start += existing.len() + 1;
+        }
+
+        let start = self.abbreviations.len();
+        self.abbreviations.extend_from_slice(name.as_bytes());
+        self.abbreviations.push(0);
+        u8::try_from(start).map_err(|_| invalid("abbreviations too long"))
+    }
+}
+
+fn invalid(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::line::{Month, Weekday};
+    use crate::posix::{PosixDay, PosixDst, PosixRule};
+
+    fn timespan(utc_offset: i64, dst_offset: i64, name: &str) -> FixedTimespan {
+        FixedTimespan {
+            utc_offset,
+            dst_offset,
+            name: name.to_owned(),
+        }
+    }
+
+    fn header(version: u8, counts: [u32; 6]) -> Vec<u8> {
+        let mut header = b"TZif".to_vec();
+        header.push(version);
+        header.extend_from_slice(&[0; 15]);
+        for count in counts {
+            header.extend_from_slice(&count.to_be_bytes());
+        }
+        header
+    }
+
+    #[test]
+    fn no_transitions() {
+        let timespans = FixedTimespanSet {
+            first: timespan(0, 0, "UTC"),
+            rest: vec![],
+        };
+        let footer = PosixTz {
+            std: timespan(0, 0, "UTC"),
+            dst: None,
+        };
+
+        let mut file = Vec::new();
+        write_tzif(&mut file, &timespans, Some(&footer)).unwrap();
+
+        let mut expected = header(b'2', [0, 0, 0, 0, 1, 1]);
+        expected.extend_from_slice(&[0; 7]);
+        expected.extend(header(b'2', [0, 0, 0, 0, 1, 4]));
+        expected.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+        expected.extend_from_slice(b"UTC\0\nUTC0\n");
+        assert_eq!(file, expected);
+    }
+
+    #[test]
+    fn shared_types() {
+        let timespans = FixedTimespanSet {
+            first: timespan(-17762, 0, "LMT"),
+            rest: vec![
+                (-2717650800, timespan(-18000, 0, "EST")),
+                (-1633280400, timespan(-18000, 3600, "EDT")),
+                (-1615140000, timespan(-18000, 0, "EST")),
+            ],
+        };
+
+        let mut file = Vec::new();
+        write_tzif(&mut file, &timespans, None).unwrap();
+
+        let mut expected = header(b'2', [0, 0, 0, 0, 1, 1]);
+        expected.extend_from_slice(&[0; 7]);
+        expected.extend(header(b'2', [0, 0, 0, 3, 3, 12]));
+        for time in [-2717650800_i64, -1633280400, -1615140000] {
+            expected.extend_from_slice(&time.to_be_bytes());
+        }
+        expected.extend_from_slice(&[1, 2, 1]);
+        for (offset, is_dst, abbreviation) in [(-17762_i32, 0, 0), (-18000, 0, 4), (-14400, 1, 8)] {
+            expected.extend_from_slice(&offset.to_be_bytes());
+            expected.extend_from_slice(&[is_dst, abbreviation]);
+        }
+        expected.extend_from_slice(b"LMT\0EST\0EDT\0\n\n");
+        assert_eq!(file, expected);
+    }
+
+    #[test]
+    fn version_3_footer() {
+        let timespans = FixedTimespanSet {
+            first: timespan(-7200, 0, "-02"),
+            rest: vec![],
+        };
+        let footer = PosixTz {
+            std: timespan(-7200, 0, "-02"),
+            dst: Some(PosixDst {
+                timespan: timespan(-7200, 3600, "-01"),
+                start: PosixRule {
+                    day: PosixDay::MonthWeekday(Month::March, 5, Weekday::Sunday),
+                    time: -2 * 60 * 60,
+                },
+                end: PosixRule {
+                    day: PosixDay::MonthWeekday(Month::October, 5, Weekday::Sunday),
+                    time: -60 * 60,
+                },
+            }),
+        };
+
+        let mut file = Vec::new();
+        write_tzif(&mut file, &timespans, Some(&footer)).unwrap();
+        assert_eq!(&file[..5], b"TZif3");
+        assert!(file.ends_with(b"\n<-02>2<-01>,M3.5.0/-2,M10.5.0/-1\n"));
+    }
+}
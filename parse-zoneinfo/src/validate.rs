@@ -0,0 +1,563 @@
+//! Checking a built Table for mistakes that still parse.
+//!
+//! A table that was built without errors can still describe time zones that
+//! don’t make sense: a ruleset that no zone uses, a continuation line that
+//! ends before the one above it, or an abbreviation that zic would warn
+//! about. Some of these are harmless, while others make the `transitions`
+//! module panic or compute odd timespans, so it’s worth catching them when
+//! editing the source files.
+//!
+//! ### Example
+//!
+//! ```
+//! use parse_zoneinfo::line::Line;
+//! use parse_zoneinfo::table::TableBuilder;
+//! use parse_zoneinfo::validate::{Diagnostic, Validate};
+//!
+//! let mut builder = TableBuilder::new();
+//! for line in [
+//!     "Rule  Unused  1990  only  -  Apr  1  2:00  1:00  D",
+//!     "Zone  Pacific/Auckland  11:39:04  -  LMT  1868  Nov  2",
+//!     "Link  Pacific/Aukland  Antarctica/McMurdo",
+//! ] {
+//!     builder.add_line(Line::new(line)?).unwrap();
+//! }
+//!
+//! let diagnostics = builder.build().validate();
+//! assert_eq!(diagnostics.len(), 2);
+//! assert_eq!(
+//!     diagnostics[0].to_string(),
+//!     "link \"Antarctica/McMurdo\" refers to \"Pacific/Aukland\", which isn't a zone"
+//! );
+//! assert_eq!(diagnostics[1], Diagnostic::UnusedRuleset { name: "Unused".to_owned() });
+//! # Ok::<(), parse_zoneinfo::line::Error>(())
+//! ```
+
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+use std::ops::Range;
+
+use crate::line::{ChangeTime, Year};
+use crate::table::{Format, RuleInfo, Saving, Table, ZoneInfo};
+use crate::transitions::YEARS;
+
+/// Trait to put the `validate` method on Tables.
+pub trait Validate {
+    /// Checks the table for mistakes, returning a diagnostic for each one
+    /// found. These are sorted by the kind of problem, then by the name of
+    /// the zone, link, or ruleset it was found in.
+    fn validate(&self) -> Vec<Diagnostic>;
+}
+
+/// A problem found in a table.
+///
+/// The lines of a zone and the rules of a ruleset are identified by their
+/// index in `Table::zonesets` and `Table::rulesets`, counting from 0.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone)]
+pub enum Diagnostic {
+    /// A link refers to a name that isn’t a zone. Links to other links count
+    /// too, as they aren’t followed.
+    LinkToMissingZone { link: String, target: String },
+
+    /// A line of a zone that isn’t its last has no `UNTIL` time, so the
+    /// lines after it never apply.
+    MissingUntil { zone: String, index: usize },
+
+    /// A line of a zone ends at or before the time the line before it does.
+    UntilNotIncreasing { zone: String, index: usize },
+
+    /// A line of a zone refers to a ruleset that doesn’t exist.
+    MissingRuleset {
+        zone: String,
+        index: usize,
+        ruleset: String,
+    },
+
+    /// The format of a line of a zone has a `%s` that needs letters from
+    /// rules, but it either uses no ruleset or none of the rules of its
+    /// ruleset are in effect while it applies.
+    MissingLetters { zone: String, index: usize },
+
+    /// An abbreviation that a line of a zone can produce breaks the rules
+    /// of the tz database.
+    InvalidAbbreviation {
+        zone: String,
+        index: usize,
+        abbreviation: String,
+        problem: AbbreviationProblem,
+    },
+
+    /// No zone uses a ruleset.
+    UnusedRuleset { name: String },
+
+    /// A rule of a ruleset doesn’t apply to any of the years that
+    /// transitions are computed for.
+    RuleNeverApplies { ruleset: String, index: usize },
+}
+
+/// The way in which a time zone abbreviation is invalid.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Copy, Clone)]
+pub enum AbbreviationProblem {
+    /// It has fewer than 3 characters.
+    TooShort,
+    /// It has more than 6 characters.
+    TooLong,
+    /// It has characters other than ASCII letters and digits, `-` and `+`.
+    InvalidCharacters,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Diagnostic::LinkToMissingZone { link, target } => write!(
+                f,
+                "link \"{}\" refers to \"{}\", which isn't a zone",
+                link, target
+            ),
+            Diagnostic::MissingUntil { zone, index } => write!(
+                f,
+                "line {} of zone \"{}\" has no end time but isn't its last",
+                index, zone
+            ),
+            Diagnostic::UntilNotIncreasing { zone, index } => write!(
+                f,
+                "line {} of zone \"{}\" doesn't end after the line before it",
+                index, zone
+            ),
+            Diagnostic::MissingRuleset {
+                zone,
+                index,
+                ruleset,
+            } => write!(
+                f,
+                "line {} of zone \"{}\" refers to the ruleset \"{}\", which isn't defined",
+                index, zone, ruleset
+            ),
+            Diagnostic::MissingLetters { zone, index } => write!(
+                f,
+                "line {} of zone \"{}\" has a format with %s, but no rules supply letters for it",
+                index, zone
+            ),
+            Diagnostic::InvalidAbbreviation {
+                zone,
+                index,
+                abbreviation,
+                problem,
+            } => write!(
+                f,
+                "line {} of zone \"{}\" has the abbreviation \"{}\", which {}",
+                index, zone, abbreviation, problem
+            ),
+            Diagnostic::UnusedRuleset { name } => {
+                write!(f, "ruleset \"{}\" isn't used by any zone", name)
+            }
+            Diagnostic::RuleNeverApplies { ruleset, index } => write!(
+                f,
+                "rule {} of ruleset \"{}\" doesn't apply to any year from {} to {}",
+                index,
+                ruleset,
+                YEARS.start,
+                YEARS.end - 1
+            ),
+        }
+    }
+}
+
+impl fmt::Display for AbbreviationProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AbbreviationProblem::TooShort => write!(f, "has fewer than 3 characters"),
+            AbbreviationProblem::TooLong => write!(f, "has more than 6 characters"),
+            AbbreviationProblem::InvalidCharacters => {
+                write!(f, "has characters other than letters, digits, - and +")
+            }
+        }
+    }
+}
+
+impl Validate for Table {
+    fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for (link, target) in &self.links {
+            if !self.zonesets.contains_key(target) {
+                diagnostics.push(Diagnostic::LinkToMissingZone {
+                    link: link.clone(),
+                    target: target.clone(),
+                });
+            }
+        }
+
+        let mut used_rulesets = HashSet::new();
+        for (name, zoneset) in &self.zonesets {
+            let mut start = None;
+            for (index, info) in zoneset.iter().enumerate() {
+                let end = end_timestamp(info);
+                let rules = match info.saving {
+                    Saving::Multiple(ref ruleset) => {
+                        used_rulesets.insert(ruleset);
+                        match self.rulesets.get(ruleset) {
+                            Some(rules) => Some(&rules[..]),
+                            None => {
+                                diagnostics.push(Diagnostic::MissingRuleset {
+                                    zone: name.clone(),
+                                    index,
+                                    ruleset: ruleset.clone(),
+                                });
+                                continue;
+                            }
+                        }
+                    }
+                    _ => None,
+                };
+                let rules = rules.map_or_else(Vec::new, |rules| {
+                    rules_in_effect(rules, info.offset, start, end)
+                });
+                check_format(name, index, info, &rules, &mut diagnostics);
+                start = end;
+            }
+            check_until_times(name, zoneset, &mut diagnostics);
+        }
+
+        for (name, rules) in &self.rulesets {
+            if !used_rulesets.contains(name) {
+                diagnostics.push(Diagnostic::UnusedRuleset { name: name.clone() });
+            }
+            for (index, rule) in rules.iter().enumerate() {
+                if !overlaps(rule, &YEARS) {
+                    diagnostics.push(Diagnostic::RuleNeverApplies {
+                        ruleset: name.clone(),
+                        index,
+                    });
+                }
+            }
+        }
+
+        diagnostics.sort();
+        diagnostics
+    }
+}
+
+/// Returns the rules that take effect while a line of a zone applies, from
+/// its start to its end as UTC timestamps, along with the one that’s still in
+/// effect when it starts. The times are only as exact as the offsets allow,
+/// as this ignores the DST in effect.
+fn rules_in_effect(
+    rules: &[RuleInfo],
+    utc_offset: i64,
+    start: Option<i64>,
+    end: Option<i64>,
+) -> Vec<&RuleInfo> {
+    let mut in_effect = Vec::new();
+    let mut latest_before_start = None;
+    for year in YEARS {
+        for rule in rules
+            .iter()
+            .filter(|rule| overlaps(rule, &(year..year + 1)))
+        {
+            let time = rule.absolute_datetime(year, utc_offset, 0);
+            if end.map_or(false, |end| time >= end) {
+                continue;
+            }
+            match start {
+                Some(start) if time < start => {
+                    if latest_before_start.map_or(true, |(latest, _)| time >= latest) {
+                        latest_before_start = Some((time, rule));
+                    }
+                }
+                _ => in_effect.push(rule),
+            }
+        }
+    }
+    in_effect.extend(latest_before_start.map(|(_, rule)| rule));
+    in_effect
+}
+
+/// Checks the format of a line of a zone against the rules in effect while
+/// it applies.
+fn check_format(
+    zone: &str,
+    index: usize,
+    info: &ZoneInfo,
+    rules: &[&RuleInfo],
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let abbreviations = match info.format {
+        Format::Constant(ref s) => BTreeSet::from([s.clone()]),
+        Format::Alternate {
+            ref standard,
+            ref dst,
+        } => BTreeSet::from([standard.clone(), dst.clone()]),
+        Format::Placeholder(ref s) => {
+            if rules.is_empty() {
+                diagnostics.push(Diagnostic::MissingLetters {
+                    zone: zone.to_owned(),
+                    index,
+                });
+            }
+            // Rules without letters replace `%s` with nothing.
+            rules
+                .iter()
+                .map(|rule| s.replace("%s", rule.letters.as_deref().unwrap_or_default()))
+                .collect()
+        }
+        // Numeric abbreviations are always valid.
+        Format::Offset => BTreeSet::new(),
+    };
+
+    for abbreviation in abbreviations {
+        if let Some(problem) = abbreviation_problem(&abbreviation) {
+            diagnostics.push(Diagnostic::InvalidAbbreviation {
+                zone: zone.to_owned(),
+                index,
+                abbreviation,
+                problem,
+            });
+        }
+    }
+}
+
+/// Checks that every line of a zone but the last ends, and that each one
+/// ends after the one before it. Like zic, this compares the local times
+/// as they’re written, ignoring the offsets in effect.
+fn check_until_times(zone: &str, zoneset: &[ZoneInfo], diagnostics: &mut Vec<Diagnostic>) {
+    let mut previous = None;
+    for (index, info) in zoneset.iter().enumerate() {
+        let Some(end_time) = info.end_time else {
+            if index != zoneset.len() - 1 {
+                diagnostics.push(Diagnostic::MissingUntil {
+                    zone: zone.to_owned(),
+                    index,
+                });
+            }
+            continue;
+        };
+
+        // Only years given as numbers can be turned into timestamps.
+        if until_year(&end_time).is_none() {
+            continue;
+        }
+        let timestamp = end_time.to_timestamp(0, 0);
+        if previous.map_or(false, |previous| timestamp <= previous) {
+            diagnostics.push(Diagnostic::UntilNotIncreasing {
+                zone: zone.to_owned(),
+                index,
+            });
+        }
+        previous = Some(timestamp);
+    }
+}
+
+/// Returns the year of a time a line of a zone ends at, if it’s a number.
+fn until_year(time: &ChangeTime) -> Option<i64> {
+    let (ChangeTime::UntilYear(year)
+    | ChangeTime::UntilMonth(year, ..)
+    | ChangeTime::UntilDay(year, ..)
+    | ChangeTime::UntilTime(year, ..)) = time;
+    match year {
+        Year::Number(year) => Some(*year),
+        _ => None,
+    }
+}
+
+/// Returns the UTC timestamp a line of a zone ends at, ignoring any DST.
+fn end_timestamp(info: &ZoneInfo) -> Option<i64> {
+    let end_time = info.end_time.as_ref()?;
+    until_year(end_time)?;
+    Some(end_time.to_timestamp(info.offset, 0))
+}
+
+/// Returns whether a rule applies to any of the given years.
+fn overlaps(rule: &RuleInfo, years: &Range<i64>) -> bool {
+    let from = match rule.from_year {
+        Year::Number(from) => from,
+        Year::Minimum => i64::MIN,
+        Year::Maximum => i64::MAX,
+    };
+    let to = match rule.to_year {
+        None => from,
+        Some(Year::Number(to)) => to,
+        Some(Year::Minimum) => i64::MIN,
+        Some(Year::Maximum) => i64::MAX,
+    };
+    from <= to && from < years.end && to >= years.start
+}
+
+/// Returns why an abbreviation breaks the rules of the tz database, as
+/// described in its `theory.html` file and checked by zic.
+fn abbreviation_problem(abbreviation: &str) -> Option<AbbreviationProblem> {
+    let valid = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '+';
+    if !abbreviation.chars().all(valid) {
+        Some(AbbreviationProblem::InvalidCharacters)
+    } else if abbreviation.len() < 3 {
+        Some(AbbreviationProblem::TooShort)
+    } else if abbreviation.len() > 6 {
+        Some(AbbreviationProblem::TooLong)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::line::Line;
+    use crate::table::TableBuilder;
+
+    fn validate(lines: &[&str]) -> Vec<Diagnostic> {
+        let mut builder = TableBuilder::new();
+        for line in lines {
+            builder.add_line(Line::new(line).unwrap()).unwrap();
+        }
+        builder.build().validate()
+    }
+
+    #[test]
+    fn valid() {
+        let diagnostics = validate(&[
+            "Rule  NZ  1974  only  -  Nov  Sun>=1  2:00s  1:00  D",
+            "Rule  NZ  1975  max   -  Feb  lastSun  2:00s  0  S",
+            "Zone  Pacific/Auckland  11:39:04  -  LMT  1868  Nov  2",
+            "                        12:00  NZ  NZ%sT",
+            "Link  Pacific/Auckland  Antarctica/McMurdo",
+            "Zone  Etc/UTC  0  -  UTC",
+        ]);
+        assert_eq!(diagnostics, []);
+    }
+
+    #[test]
+    fn until_times() {
+        let diagnostics = validate(&[
+            "Zone  Europe/Nowhere  0:10  -  LMT  1900",
+            "                      1:00  -  CET",
+            "                      2:00  -  EET  1950",
+            "                      3:00  -  MSK  1949 Dec 31",
+            "                      4:00  -  +04",
+        ]);
+        assert_eq!(
+            diagnostics,
+            [
+                Diagnostic::MissingUntil {
+                    zone: "Europe/Nowhere".to_owned(),
+                    index: 1,
+                },
+                Diagnostic::UntilNotIncreasing {
+                    zone: "Europe/Nowhere".to_owned(),
+                    index: 3,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rulesets() {
+        let diagnostics = validate(&[
+            "Rule  Old  1700  1750  -  Apr  1  2:00  1:00  -",
+            "Rule  Old  1990  1980  -  Oct  1  2:00  0  -",
+            "Rule  Old  1990  max  -  Oct  1  2:00  0  -",
+            "Zone  America/Nowhere  -5:00  Old  E%sT",
+            "Zone  America/Elsewhere  -5:00  -  LMT  1900",
+            "                         -5:00  Gone  EST",
+        ]);
+        assert_eq!(
+            diagnostics,
+            [
+                Diagnostic::MissingRuleset {
+                    zone: "America/Elsewhere".to_owned(),
+                    index: 1,
+                    ruleset: "Gone".to_owned(),
+                },
+                Diagnostic::InvalidAbbreviation {
+                    zone: "America/Nowhere".to_owned(),
+                    index: 0,
+                    abbreviation: "ET".to_owned(),
+                    problem: AbbreviationProblem::TooShort,
+                },
+                Diagnostic::RuleNeverApplies {
+                    ruleset: "Old".to_owned(),
+                    index: 0,
+                },
+                Diagnostic::RuleNeverApplies {
+                    ruleset: "Old".to_owned(),
+                    index: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn abbreviations() {
+        let diagnostics = validate(&[
+            "Rule  Letters  1990  only  -  Apr  1  2:00  1:00  Summer",
+            "Rule  Letters  1990  only  -  Oct  1  2:00  0  S",
+            "Zone  Asia/Nowhere  5:00  -  +05/Z  1980",
+            "                    6:00  Letters  %sT  2000",
+            "                    7:00  -  Asia_T",
+        ]);
+        let problems = diagnostics
+            .iter()
+            .map(|diagnostic| match diagnostic {
+                Diagnostic::InvalidAbbreviation {
+                    index,
+                    abbreviation,
+                    problem,
+                    ..
+                } => (*index, &abbreviation[..], *problem),
+                _ => panic!("unexpected diagnostic {diagnostic:?}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            problems,
+            [
+                (0, "Z", AbbreviationProblem::TooShort),
+                (1, "ST", AbbreviationProblem::TooShort),
+                (1, "SummerT", AbbreviationProblem::TooLong),
+                (2, "Asia_T", AbbreviationProblem::InvalidCharacters),
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_letters() {
+        let diagnostics = validate(&[
+            "Rule  Later  1990  only  -  Apr  1  2:00  1:00  S",
+            "Rule  Later  1990  only  -  Oct  1  2:00  0  -",
+            "Zone  Africa/Nowhere  1:00  -  W%sT",
+            "Zone  Africa/Elsewhere  1:00  Later  W%sT  1950",
+            "                        1:00  Later  W%sT",
+        ]);
+        assert_eq!(
+            diagnostics,
+            [
+                Diagnostic::MissingLetters {
+                    zone: "Africa/Elsewhere".to_owned(),
+                    index: 0,
+                },
+                Diagnostic::MissingLetters {
+                    zone: "Africa/Nowhere".to_owned(),
+                    index: 0,
+                },
+                Diagnostic::InvalidAbbreviation {
+                    zone: "Africa/Elsewhere".to_owned(),
+                    index: 1,
+                    abbreviation: "WT".to_owned(),
+                    problem: AbbreviationProblem::TooShort,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn letters_of_earlier_rules() {
+        // The war time of 1942 is still in effect from 1944 on.
+        let diagnostics = validate(&[
+            "Rule  US  1942  only  -  Feb  9  2:00  1:00  W",
+            "Rule  US  1945  only  -  Sep  30  2:00  0  S",
+            "Zone  America/Phoenix  -7:00  US  M%sT  1944 Jan  1  0:01",
+            "                       -7:00  -   MST  1944 Apr  1  0:01",
+            "                       -7:00  US  M%sT  1944 Oct  1  0:01",
+            "                       -7:00  -   MST",
+        ]);
+        assert_eq!(diagnostics, []);
+    }
+}
@@ -0,0 +1,65 @@
+use std::fs::{self, File};
+use std::io::BufReader;
+
+use parse_zoneinfo::line::Line;
+use parse_zoneinfo::source::write_source;
+use parse_zoneinfo::table::{Table, TableBuilder};
+use parse_zoneinfo::FILES;
+
+// `leapseconds` and `tzdata.zi` are built by `make`, so they aren't in the tz
+// repository. There is an excerpt of the latter among the fixtures.
+const TZDATA_ZI: &str = "tests/fixtures/tzdata.zi";
+
+#[test]
+fn lines() {
+    let paths = FILES
+        .iter()
+        .map(|name| format!("../chrono-tz/tz/{name}"))
+        .chain([TZDATA_ZI.to_owned()]);
+    for path in paths {
+        let text = fs::read_to_string(&path).unwrap();
+        for (number, text) in text.lines().enumerate() {
+            let line = Line::new(text).unwrap();
+            let written = line.to_string();
+            assert_eq!(Line::new(&written), Ok(line), "{path}:{}", number + 1);
+        }
+    }
+
+    // The lines of `leapseconds`
+    for text in [
+        "Leap\t1972\tJun\t30\t23:59:60\t+\tS",
+        "Leap\t2016\tDec\t31\t23:59:60\t+\tS",
+        "Expires\t2026\tJun\t28\t00:00:00",
+    ] {
+        let line = Line::new(text).unwrap();
+        let written = line.to_string();
+        assert_eq!(Line::new(&written), Ok(line), "{text}");
+    }
+}
+
+#[test]
+fn source_files() {
+    let mut builder = TableBuilder::new();
+    for name in FILES {
+        let path = format!("../chrono-tz/tz/{name}");
+        let file = File::open(&path).unwrap();
+        builder.add_file(&path, BufReader::new(file)).unwrap();
+    }
+    let table = builder.build();
+
+    let mut text = Vec::new();
+    write_source(&mut text, &table).unwrap();
+    let mut builder = TableBuilder::new();
+    builder.add_file("written", &text[..]).unwrap();
+    assert_eq!(builder.build(), table);
+}
+
+#[test]
+fn tzdata_zi() {
+    let file = File::open(TZDATA_ZI).unwrap();
+    let table = Table::from_tzdata_zi(BufReader::new(file)).unwrap();
+
+    let mut text = Vec::new();
+    write_source(&mut text, &table).unwrap();
+    assert_eq!(Table::from_tzdata_zi(&text[..]).unwrap(), table);
+}
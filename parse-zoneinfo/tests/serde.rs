@@ -0,0 +1,42 @@
+#![cfg(feature = "serde")]
+
+use std::fs::File;
+use std::io::BufReader;
+
+use parse_zoneinfo::table::{Table, TableBuilder};
+use parse_zoneinfo::transitions::{FixedTimespanSet, TableTransitions};
+use parse_zoneinfo::FILES;
+
+fn source_table() -> Table {
+    let mut builder = TableBuilder::new();
+    for name in FILES {
+        let path = format!("../chrono-tz/tz/{name}");
+        let file = File::open(&path).unwrap();
+        builder.add_file(&path, BufReader::new(file)).unwrap();
+    }
+    builder.build()
+}
+
+#[test]
+fn table_round_trip() {
+    let table = source_table();
+
+    let json = serde_json::to_string(&table).unwrap();
+    let parsed = serde_json::from_str::<Table>(&json).unwrap();
+    assert_eq!(parsed, table);
+    assert_eq!(
+        parsed.timespans("Europe/London"),
+        table.timespans("Europe/London")
+    );
+}
+
+#[test]
+fn timespans_round_trip() {
+    let table = source_table();
+    let timespans = table.timespans("America/New_York").unwrap();
+
+    let json = serde_json::to_string(&timespans).unwrap();
+    assert!(json.starts_with(r#"{"first":{"utc_offset":-17762,"dst_offset":0,"name":"LMT"},"#));
+    let parsed = serde_json::from_str::<FixedTimespanSet>(&json).unwrap();
+    assert_eq!(parsed, timespans);
+}
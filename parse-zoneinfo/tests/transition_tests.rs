@@ -812,6 +812,8 @@ Zone    Asia/Dushanbe   4:35:12 -   LMT 1924 May  2
             Line::Continuation(cont) => table.add_continuation_line(cont).unwrap(),
             Line::Rule(rule) => table.add_rule_line(rule).unwrap(),
             Line::Link(link) => table.add_link_line(link).unwrap(),
+            Line::Leap(leap) => table.add_leap_line(leap).unwrap(),
+            Line::Expires(expires) => table.add_expires_line(expires).unwrap(),
             Line::Space => {}
         }
     }
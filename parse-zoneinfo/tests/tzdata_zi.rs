@@ -0,0 +1,44 @@
+use std::fs::File;
+use std::io::BufReader;
+
+use parse_zoneinfo::line::Line;
+use parse_zoneinfo::table::{Table, TableBuilder};
+use parse_zoneinfo::transitions::TableTransitions;
+use parse_zoneinfo::validate::Validate;
+use parse_zoneinfo::FILES;
+
+// `tzdata.zi` is built by `make`, so it isn't in the tz repository. The
+// fixture holds a few zones of the one from the release in `chrono-tz/tz`,
+// along with their rules and links.
+fn fixture() -> Table {
+    let file = File::open("tests/fixtures/tzdata.zi").unwrap();
+    Table::from_tzdata_zi(BufReader::new(file)).unwrap()
+}
+
+#[test]
+fn same_as_source_files() {
+    let mut builder = TableBuilder::new();
+    for file in FILES {
+        let text = std::fs::read_to_string(format!("../chrono-tz/tz/{file}")).unwrap();
+        for line in text.lines() {
+            builder.add_line(Line::new(line).unwrap()).unwrap();
+        }
+    }
+    let source = builder.build();
+
+    let zi = fixture();
+    assert_eq!(zi.version.as_deref(), Some("2025b"));
+    assert_eq!(zi.zonesets.len(), 4);
+
+    for name in zi.zonesets.keys() {
+        assert_eq!(zi.timespans(name), source.timespans(name), "{name}");
+    }
+    for name in zi.links.keys() {
+        assert!(source.get_zoneset(name).is_some(), "{name}");
+    }
+}
+
+#[test]
+fn tzdata_zi_is_valid() {
+    assert_eq!(fixture().validate(), []);
+}